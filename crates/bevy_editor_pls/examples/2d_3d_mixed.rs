@@ -10,7 +10,7 @@ fn main() {
 
 fn setup_2d(mut commands: Commands) {
     commands.spawn((
-        Camera2d::default(),
+        Camera2d,
         Camera {
             order: 1,
             ..Default::default()
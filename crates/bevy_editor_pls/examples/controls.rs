@@ -16,10 +16,10 @@ fn editor_controls() -> controls::EditorControls {
 
     editor_controls.insert(
         controls::Action::PlayPauseEditor,
-        controls::Binding {
-            input: controls::UserInput::Single(controls::Button::Keyboard(KeyCode::Escape)),
-            conditions: vec![controls::BindingCondition::ListeningForText(false)],
-        },
+        controls::Binding::new(controls::UserInput::Single(controls::Button::Keyboard(
+            KeyCode::Escape,
+        )))
+        .with_conditions([controls::BindingCondition::ListeningForText(false)]),
     );
 
     editor_controls
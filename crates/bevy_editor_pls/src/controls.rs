@@ -1,19 +1,45 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, VecDeque};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use bevy::ecs::system::{BoxedSystem, SystemId};
+use bevy::reflect::{DynamicEnum, DynamicVariant, TypeInfo, Typed};
 use bevy::{prelude::*, utils::HashMap};
-use bevy_editor_pls_core::{editor_window::EditorWindow, Editor, EditorEvent};
+use bevy_editor_pls_core::{
+    editor::EditorInternalState,
+    editor_window::{EditorWindow, EditorWindowContext},
+    layout::{self, LayoutError},
+    menu::MenuItem,
+    undo::UndoStack,
+    AddMenuItem, AddPaletteCommand, Editor, EditorEvent, EditorPersistence, Notifications,
+};
+#[cfg(feature = "default_windows")]
+use bevy_editor_pls_default_windows::time_control::{self, TimeControl};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Button {
     Keyboard(KeyCode),
     Mouse(MouseButton),
+    /// A button on any of the connected gamepads
+    Gamepad(GamepadButton),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum UserInput {
     Single(Button),
     Chord(Vec<Button>),
+    /// Triggers once the button has been held down for the duration
+    Hold(Button, Duration),
+    /// Two presses within [`EditorControls::double_tap_timeout`]
+    DoubleTap(Button),
+    /// Buttons pressed one after another, each within [`EditorControls::sequence_timeout`] of the previous one
+    Sequence(Vec<Button>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum BindingCondition {
     InViewport(bool),
     EditorActive(bool),
@@ -21,6 +47,11 @@ pub enum BindingCondition {
 }
 
 impl BindingCondition {
+    /// Whether no state of the editor fulfills both conditions
+    fn excludes(&self, other: &BindingCondition) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other) && self != other
+    }
+
     fn evaluate(&self, editor: &Editor) -> bool {
         match *self {
             BindingCondition::InViewport(in_viewport) => {
@@ -52,72 +83,175 @@ impl std::fmt::Display for BindingCondition {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct Binding {
     pub input: UserInput,
     pub conditions: Vec<BindingCondition>,
+    /// By default a binding only triggers if no modifier keys besides its own are held,
+    /// so that e.g. `S` doesn't trigger while pressing `Ctrl+S`
+    pub allow_extra_modifiers: bool,
 }
 
-impl From<UserInput> for Binding {
-    fn from(input: UserInput) -> Self {
+impl Binding {
+    pub fn new(input: UserInput) -> Self {
         Binding {
             input,
             conditions: Vec::new(),
+            allow_extra_modifiers: false,
         }
     }
+
+    /// Only trigger the binding while all `conditions` hold
+    pub fn with_conditions(
+        mut self,
+        conditions: impl IntoIterator<Item = BindingCondition>,
+    ) -> Self {
+        self.conditions.extend(conditions);
+        self
+    }
+
+    /// Also trigger the binding while other modifier keys are held
+    pub fn allow_extra_modifiers(mut self) -> Self {
+        self.allow_extra_modifiers = true;
+        self
+    }
+}
+
+impl From<UserInput> for Binding {
+    fn from(input: UserInput) -> Self {
+        Binding::new(input)
+    }
+}
+
+/// The input devices and recent presses the bindings are evaluated against
+struct InputState<'a> {
+    keyboard_input: &'a ButtonInput<KeyCode>,
+    mouse_input: &'a ButtonInput<MouseButton>,
+    gamepads: Vec<&'a Gamepad>,
+    history: &'a InputHistory,
+    /// Real time of this and the previous frame
+    now: Duration,
+    previous: Duration,
 }
 
 impl Button {
-    fn just_pressed(
-        &self,
-        keyboard_input: &ButtonInput<KeyCode>,
-        mouse_input: &ButtonInput<MouseButton>,
-    ) -> bool {
+    fn just_pressed(&self, input: &InputState) -> bool {
         match self {
-            Button::Keyboard(code) => keyboard_input.just_pressed(*code),
-            Button::Mouse(button) => mouse_input.just_pressed(*button),
+            Button::Keyboard(code) => input.keyboard_input.just_pressed(*code),
+            Button::Mouse(button) => input.mouse_input.just_pressed(*button),
+            Button::Gamepad(button) => input
+                .gamepads
+                .iter()
+                .any(|gamepad| gamepad.just_pressed(*button)),
         }
     }
-    fn pressed(
-        &self,
-        keyboard_input: &ButtonInput<KeyCode>,
-        mouse_input: &ButtonInput<MouseButton>,
-    ) -> bool {
+    fn pressed(&self, input: &InputState) -> bool {
         match self {
-            Button::Keyboard(code) => keyboard_input.pressed(*code),
-            Button::Mouse(button) => mouse_input.pressed(*button),
+            Button::Keyboard(code) => input.keyboard_input.pressed(*code),
+            Button::Mouse(button) => input.mouse_input.pressed(*button),
+            Button::Gamepad(button) => input
+                .gamepads
+                .iter()
+                .any(|gamepad| gamepad.pressed(*button)),
         }
     }
 }
 
 impl UserInput {
-    fn just_pressed(
-        &self,
-        keyboard_input: &ButtonInput<KeyCode>,
-        mouse_input: &ButtonInput<MouseButton>,
-    ) -> bool {
+    fn just_pressed(&self, input: &InputState, controls: &EditorControls) -> bool {
         match self {
-            UserInput::Single(single) => single.just_pressed(keyboard_input, mouse_input),
+            UserInput::Single(single) => single.just_pressed(input),
             UserInput::Chord(chord) => match chord.as_slice() {
                 [modifiers @ .., final_key] => {
-                    let modifiers_pressed = modifiers
-                        .iter()
-                        .all(|key| key.pressed(keyboard_input, mouse_input));
-                    modifiers_pressed && final_key.just_pressed(keyboard_input, mouse_input)
+                    let modifiers_pressed = modifiers.iter().all(|key| key.pressed(input));
+                    modifiers_pressed && final_key.just_pressed(input)
                 }
                 [] => false,
             },
+            UserInput::Hold(button, duration) => {
+                let Some(pressed_at) = input.history.last_press(button) else {
+                    return false;
+                };
+                // only the frame in which the duration is reached triggers
+                button.pressed(input)
+                    && input.now.saturating_sub(pressed_at) >= *duration
+                    && input.previous.saturating_sub(pressed_at) < *duration
+            }
+            UserInput::DoubleTap(button) => {
+                let presses = input
+                    .history
+                    .repeated_presses(button, controls.double_tap_timeout);
+                // a triple tap doesn't trigger twice
+                button.just_pressed(input) && presses.is_multiple_of(2)
+            }
+            UserInput::Sequence(sequence) => {
+                sequence.last().is_some_and(|last| last.just_pressed(input))
+                    && input.history.ends_with(sequence, controls.sequence_timeout)
+            }
+        }
+    }
+}
+
+/// How many presses the [`InputHistory`] remembers
+const INPUT_HISTORY_LEN: usize = 32;
+
+/// The most recently pressed buttons and when they were pressed, for holds, double taps and sequences
+#[derive(Default)]
+pub(crate) struct InputHistory {
+    /// Oldest first
+    presses: VecDeque<(Button, Duration)>,
+    last_update: Duration,
+}
+
+impl InputHistory {
+    fn record(&mut self, button: Button, now: Duration) {
+        if self.presses.len() == INPUT_HISTORY_LEN {
+            self.presses.pop_front();
+        }
+        self.presses.push_back((button, now));
+    }
+
+    fn last_press(&self, button: &Button) -> Option<Duration> {
+        self.presses
+            .iter()
+            .rev()
+            .find(|(pressed, _)| pressed == button)
+            .map(|(_, time)| *time)
+    }
+
+    /// How often `button` was pressed in a row, each press within `timeout` of the next one
+    fn repeated_presses(&self, button: &Button, timeout: Duration) -> usize {
+        let mut count = 0;
+        let mut next_press = None;
+        for (pressed, time) in self.presses.iter().rev() {
+            let in_time = next_press.is_none_or(|next: Duration| next - *time <= timeout);
+            if pressed != button || !in_time {
+                break;
+            }
+            count += 1;
+            next_press = Some(*time);
+        }
+        count
+    }
+
+    /// Whether the latest presses are `sequence`, each within `timeout` of the previous one
+    fn ends_with(&self, sequence: &[Button], timeout: Duration) -> bool {
+        if self.presses.len() < sequence.len() {
+            return false;
         }
+        let latest = self.presses.range(self.presses.len() - sequence.len()..);
+        let buttons_match = latest
+            .clone()
+            .zip(sequence)
+            .all(|((pressed, _), button)| pressed == button);
+        let times: Vec<_> = latest.map(|(_, time)| *time).collect();
+        buttons_match && times.windows(2).all(|pair| pair[1] - pair[0] <= timeout)
     }
 }
 
 impl Binding {
-    fn just_pressed(
-        &self,
-        keyboard_input: &ButtonInput<KeyCode>,
-        mouse_input: &ButtonInput<MouseButton>,
-        editor: &Editor,
-    ) -> bool {
+    fn just_pressed(&self, input: &InputState, controls: &EditorControls, editor: &Editor) -> bool {
         let can_trigger = self
             .conditions
             .iter()
@@ -126,15 +260,118 @@ impl Binding {
             return false;
         }
 
-        self.input.just_pressed(keyboard_input, mouse_input)
+        self.input.just_pressed(input, controls)
+            && (self.allow_extra_modifiers || self.input.modifiers_match(input))
+    }
+
+    /// Whether some press can trigger both bindings in the same editor state
+    fn overlaps(&self, other: &Binding) -> bool {
+        let conditions_overlap = !self.conditions.iter().any(|condition| {
+            other
+                .conditions
+                .iter()
+                .any(|other_condition| condition.excludes(other_condition))
+        });
+        conditions_overlap && self.input_overlaps(other)
+    }
+
+    fn input_overlaps(&self, other: &Binding) -> bool {
+        let plain = |input: &UserInput| matches!(input, UserInput::Single(_) | UserInput::Chord(_));
+        if !plain(&self.input) && !plain(&other.input) {
+            return self.input == other.input;
+        }
+
+        let (Some((button, _)), Some((other_button, _))) =
+            (self.input.trigger(), other.input.trigger())
+        else {
+            return false;
+        };
+        if button != other_button {
+            return false;
+        }
+        // the modifiers of the smallest set of buttons pressed which triggers both
+        let pressed: Vec<_> = self
+            .input
+            .bound_modifiers()
+            .into_iter()
+            .chain(other.input.bound_modifiers())
+            .collect();
+        let accepts = |binding: &Binding| {
+            let bound = binding.input.bound_modifiers();
+            binding.allow_extra_modifiers || pressed.iter().all(|kind| bound.contains(kind))
+        };
+        accepts(self) && accepts(other)
     }
 }
 
-#[derive(PartialEq, Eq, Hash)]
+/// Keys which a binding requires to be held exactly, unless [`Binding::allow_extra_modifiers`] is set
+const MODIFIERS: [KeyCode; 8] = [
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::SuperLeft,
+    KeyCode::SuperRight,
+];
+
+/// The modifiers among `buttons`, with the left and right keys treated the same
+fn modifier_kinds<'a>(buttons: impl IntoIterator<Item = &'a Button>) -> Vec<usize> {
+    buttons
+        .into_iter()
+        .filter_map(|button| match button {
+            Button::Keyboard(key) => MODIFIERS.iter().position(|modifier| modifier == key),
+            _ => None,
+        })
+        .map(|position| position / 2)
+        .collect()
+}
+
+impl UserInput {
+    /// The button whose press triggers the input, and the buttons which have to be held down at that moment
+    fn trigger(&self) -> Option<(&Button, &[Button])> {
+        match self {
+            UserInput::Single(button)
+            | UserInput::Hold(button, _)
+            | UserInput::DoubleTap(button) => Some((button, [].as_slice())),
+            UserInput::Chord(chord) => chord.split_last(),
+            UserInput::Sequence(sequence) => sequence.last().map(|button| (button, [].as_slice())),
+        }
+    }
+
+    /// The modifiers which may be held when the input triggers, including the trigger button itself
+    fn bound_modifiers(&self) -> Vec<usize> {
+        self.trigger()
+            .map(|(button, held)| modifier_kinds(held.iter().chain([button])))
+            .unwrap_or_default()
+    }
+
+    /// Whether every held modifier is part of the input
+    fn modifiers_match(&self, input: &InputState) -> bool {
+        if self.trigger().is_none() {
+            return false;
+        }
+        let bound = self.bound_modifiers();
+        let pressed = MODIFIERS
+            .iter()
+            .filter(|modifier| input.keyboard_input.pressed(**modifier))
+            .map(|modifier| Button::Keyboard(*modifier))
+            .collect::<Vec<_>>();
+        modifier_kinds(&pressed)
+            .iter()
+            .all(|kind| bound.contains(kind))
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
 pub enum Action {
     PlayPauseEditor,
     PauseUnpauseTime,
     FocusSelected,
+    Undo,
+    Redo,
+    OpenCommandPalette,
 
     // maybe investigate [GizmoOptions].hotkeys
     // https://docs.rs/transform-gizmo-bevy/latest/transform_gizmo_bevy/struct.GizmoHotkeys.html
@@ -144,6 +381,20 @@ pub enum Action {
     SetGizmoModeRotate,
     #[cfg(feature = "default_windows")]
     SetGizmoModeScale,
+
+    #[cfg(feature = "default_windows")]
+    StepFrame,
+    #[cfg(feature = "default_windows")]
+    StepFrames,
+    #[cfg(feature = "default_windows")]
+    StepFixedUpdate,
+    #[cfg(feature = "default_windows")]
+    IncreaseTimeSpeed,
+    #[cfg(feature = "default_windows")]
+    DecreaseTimeSpeed,
+
+    /// An [`EditorAction`] registered by a plugin, identified by its name
+    Custom(&'static str),
 }
 
 impl std::fmt::Display for Action {
@@ -152,20 +403,50 @@ impl std::fmt::Display for Action {
             Action::PlayPauseEditor => write!(f, "Play/Pause editor"),
             Action::PauseUnpauseTime => write!(f, "Pause/Unpause time"),
             Action::FocusSelected => write!(f, "Focus Selected Entity"),
+            Action::Undo => write!(f, "Undo"),
+            Action::Redo => write!(f, "Redo"),
+            Action::OpenCommandPalette => write!(f, "Open command palette"),
             #[cfg(feature = "default_windows")]
             Action::SetGizmoModeTranslate => write!(f, "Activate translation gizmo"),
             #[cfg(feature = "default_windows")]
             Action::SetGizmoModeRotate => write!(f, "Activate rotation gizmo"),
             #[cfg(feature = "default_windows")]
             Action::SetGizmoModeScale => write!(f, "Activate scale gizmo"),
+            #[cfg(feature = "default_windows")]
+            Action::StepFrame => write!(f, "Step 1 frame"),
+            #[cfg(feature = "default_windows")]
+            Action::StepFrames => write!(f, "Step N frames"),
+            #[cfg(feature = "default_windows")]
+            Action::StepFixedUpdate => write!(f, "Run FixedUpdate once"),
+            #[cfg(feature = "default_windows")]
+            Action::IncreaseTimeSpeed => write!(f, "Increase time speed"),
+            #[cfg(feature = "default_windows")]
+            Action::DecreaseTimeSpeed => write!(f, "Decrease time speed"),
+            Action::Custom(name) => write!(f, "{name}"),
         }
     }
 }
 
 /// Resource mapping input bindings to [`Action`]s
-#[derive(Resource, Default)]
+///
+/// The bindings can be overridden in [`EditorPersistence::KEYMAP_FILE`], see [`Keymap`].
+#[derive(Resource, Debug, Clone, PartialEq)]
 pub struct EditorControls {
     pub actions: HashMap<Action, Vec<Binding>>,
+    /// Maximum time between the presses of a [`UserInput::DoubleTap`]
+    pub double_tap_timeout: Duration,
+    /// Maximum time between two steps of a [`UserInput::Sequence`]
+    pub sequence_timeout: Duration,
+}
+
+impl Default for EditorControls {
+    fn default() -> Self {
+        EditorControls {
+            actions: HashMap::default(),
+            double_tap_timeout: Duration::from_millis(300),
+            sequence_timeout: Duration::from_secs(1),
+        }
+    }
 }
 
 impl EditorControls {
@@ -176,96 +457,352 @@ impl EditorControls {
         self.actions.get(action).map_or(&[], Vec::as_slice)
     }
 
-    fn just_pressed(
-        &self,
-        action: Action,
-        keyboard_input: &ButtonInput<KeyCode>,
-        mouse_input: &ButtonInput<MouseButton>,
-        editor: &Editor,
-    ) -> bool {
-        let bindings = &self.actions.get(&action).unwrap();
-        bindings
+    fn just_pressed(&self, action: Action, input: &InputState, editor: &Editor) -> bool {
+        self.get(&action)
             .iter()
-            .any(|binding| binding.just_pressed(keyboard_input, mouse_input, editor))
+            .any(|binding| binding.just_pressed(input, self, editor))
     }
 }
 
-pub fn editor_controls_system(
+impl Action {
+    /// Name of the action in the [`Keymap`]
+    pub fn name(&self) -> String {
+        match self {
+            Action::Custom(name) => name.to_string(),
+            builtin => format!("{builtin:?}"),
+        }
+    }
+
+    /// Every builtin action, in the order they are listed in the [`ControlsWindow`].
+    /// See [`EditorActions::all`] for the list including the [`EditorAction`]s.
+    pub fn all() -> Vec<Action> {
+        #[allow(unused_mut)]
+        let mut actions = vec![
+            Action::PlayPauseEditor,
+            Action::PauseUnpauseTime,
+            Action::FocusSelected,
+            Action::Undo,
+            Action::Redo,
+            Action::OpenCommandPalette,
+        ];
+        #[cfg(feature = "default_windows")]
+        actions.extend([
+            Action::SetGizmoModeTranslate,
+            Action::SetGizmoModeRotate,
+            Action::SetGizmoModeScale,
+            Action::StepFrame,
+            Action::StepFrames,
+            Action::StepFixedUpdate,
+            Action::IncreaseTimeSpeed,
+            Action::DecreaseTimeSpeed,
+        ]);
+        actions
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn editor_controls_system(
+    mut commands: Commands,
     controls: Res<EditorControls>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mouse_input: Res<ButtonInput<MouseButton>>,
-    mut editor_events: EventWriter<EditorEvent>,
-    mut editor: ResMut<Editor>,
+    gamepads: Query<&Gamepad>,
+    time: Res<Time<Real>>,
+    mut history: Local<InputHistory>,
+    editor: Res<Editor>,
+    editor_actions: Res<EditorActions>,
 ) {
-    if controls.just_pressed(
-        Action::PlayPauseEditor,
-        &keyboard_input,
-        &mouse_input,
-        &editor,
-    ) && !editor.always_active()
+    let now = time.elapsed();
+    let previous = std::mem::replace(&mut history.last_update, now);
+    let pressed_keys = keyboard_input
+        .get_just_pressed()
+        .copied()
+        .map(Button::Keyboard);
+    let pressed_mouse = mouse_input.get_just_pressed().copied().map(Button::Mouse);
+    let pressed_gamepad = gamepads
+        .iter()
+        .flat_map(Gamepad::get_just_pressed)
+        .copied()
+        .map(Button::Gamepad);
+    for button in pressed_keys.chain(pressed_mouse).chain(pressed_gamepad) {
+        history.record(button, now);
+    }
+
+    if editor
+        .window_state::<ControlsWindow>()
+        .is_some_and(ControlsWindowState::is_capturing)
     {
-        let was_active = editor.active();
-        editor.set_active(!was_active);
-        editor_events.send(EditorEvent::Toggle {
-            now_active: !was_active,
-        });
+        return;
     }
 
-    if controls.just_pressed(
-        Action::PauseUnpauseTime,
-        &keyboard_input,
-        &mouse_input,
-        &editor,
-    ) {
-        if let Some(default_window) = editor.window_state_mut::<bevy_editor_pls_default_windows::debug_settings::DebugSettingsWindow>() {
-            default_window.pause_time = !default_window.pause_time;
+    let input = InputState {
+        keyboard_input: &keyboard_input,
+        mouse_input: &mouse_input,
+        gamepads: gamepads.iter().collect(),
+        history: &history,
+        now,
+        previous,
+    };
+    let just_pressed = |action| controls.just_pressed(action, &input, &editor);
+
+    // when extra modifiers are allowed, `Ctrl+Shift+Z` also matches the undo chord, so redo takes precedence
+    let redo_pressed = just_pressed(Action::Redo);
+
+    for action in editor_actions.all() {
+        if action == Action::Undo && redo_pressed {
+            continue;
+        }
+        if just_pressed(action) {
+            commands.queue(move |world: &mut World| run_action(world, action));
         }
     }
+}
 
-    if controls.just_pressed(
-        Action::FocusSelected,
-        &keyboard_input,
-        &mouse_input,
-        &editor,
-    ) {
-        editor_events.send(EditorEvent::FocusSelected);
+/// Performs `action` as if one of its bindings was pressed
+pub fn run_action(world: &mut World, action: Action) {
+    match action {
+        Action::PlayPauseEditor => {
+            let mut editor = world.resource_mut::<Editor>();
+            if editor.always_active() {
+                return;
+            }
+            let now_active = !editor.active();
+            editor.set_active(now_active);
+            world.send_event(EditorEvent::Toggle { now_active });
+        }
+        Action::PauseUnpauseTime => {
+            let mut time = world.resource_mut::<Time<Virtual>>();
+            if time.is_paused() {
+                time.unpause();
+            } else {
+                time.pause();
+            }
+        }
+        Action::FocusSelected => {
+            world.send_event(EditorEvent::FocusSelected);
+        }
+        Action::Undo => {
+            UndoStack::undo(world);
+        }
+        Action::Redo => {
+            UndoStack::redo(world);
+        }
+        Action::OpenCommandPalette => {
+            world
+                .resource_mut::<EditorInternalState>()
+                .open_command_palette();
+        }
+        #[cfg(feature = "default_windows")]
+        Action::SetGizmoModeTranslate => {
+            set_gizmo_modes(world, transform_gizmo_bevy::GizmoMode::all_translate());
+        }
+        #[cfg(feature = "default_windows")]
+        Action::SetGizmoModeRotate => {
+            set_gizmo_modes(world, transform_gizmo_bevy::GizmoMode::all_rotate());
+        }
+        #[cfg(feature = "default_windows")]
+        Action::SetGizmoModeScale => {
+            set_gizmo_modes(world, transform_gizmo_bevy::GizmoMode::all_scale());
+        }
+        #[cfg(feature = "default_windows")]
+        Action::StepFrame => {
+            if let Some(mut time_control) = world.get_resource_mut::<TimeControl>() {
+                time_control.step_frames(1);
+            }
+        }
+        #[cfg(feature = "default_windows")]
+        Action::StepFrames => {
+            if let Some(mut time_control) = world.get_resource_mut::<TimeControl>() {
+                let frames = time_control.step_count;
+                time_control.step_frames(frames);
+            }
+        }
+        #[cfg(feature = "default_windows")]
+        Action::StepFixedUpdate => {
+            if let Some(mut time_control) = world.get_resource_mut::<TimeControl>() {
+                time_control.step_fixed_update();
+            }
+        }
+        #[cfg(feature = "default_windows")]
+        Action::IncreaseTimeSpeed => {
+            let mut time = world.resource_mut::<Time<Virtual>>();
+            let speed = time_control::faster(time.relative_speed_f64());
+            time.set_relative_speed_f64(speed);
+        }
+        #[cfg(feature = "default_windows")]
+        Action::DecreaseTimeSpeed => {
+            let mut time = world.resource_mut::<Time<Virtual>>();
+            let speed = time_control::slower(time.relative_speed_f64());
+            time.set_relative_speed_f64(speed);
+        }
+        Action::Custom(name) => {
+            let Some(system) = world
+                .get_resource::<EditorActions>()
+                .and_then(|actions| actions.system(name))
+            else {
+                warn!("No editor action named `{name}` is registered");
+                return;
+            };
+            if let Err(error) = world.run_system(system) {
+                error!("Could not run editor action `{name}`: {error}");
+            }
+        }
     }
+}
 
-    #[cfg(feature = "default_windows")]
-    {
-        if controls.just_pressed(
-            Action::SetGizmoModeTranslate,
-            &keyboard_input,
-            &mouse_input,
-            &editor,
-        ) {
-            editor
-                .window_state_mut::<bevy_editor_pls_default_windows::gizmos::GizmoWindow>()
-                .unwrap()
-                .gizmo_modes = transform_gizmo_bevy::GizmoMode::all_translate();
-        }
-        if controls.just_pressed(
-            Action::SetGizmoModeRotate,
-            &keyboard_input,
-            &mouse_input,
-            &editor,
-        ) {
-            editor
-                .window_state_mut::<bevy_editor_pls_default_windows::gizmos::GizmoWindow>()
-                .unwrap()
-                .gizmo_modes = transform_gizmo_bevy::GizmoMode::all_rotate();
-        }
-        if controls.just_pressed(
-            Action::SetGizmoModeScale,
-            &keyboard_input,
-            &mouse_input,
-            &editor,
-        ) {
-            editor
-                .window_state_mut::<bevy_editor_pls_default_windows::gizmos::GizmoWindow>()
-                .unwrap()
-                .gizmo_modes = transform_gizmo_bevy::GizmoMode::all_scale();
+/// A user-defined action, registered with [`AddEditorAction::add_editor_action`].
+///
+/// Like the builtin [`Action`]s, it can be rebound in the [`ControlsWindow`] and the [`Keymap`] and run from the command palette.
+/// The handler is run as a one-shot system, which can also be a closure taking `&mut World`.
+///
+/// ```rust,ignore
+/// app.add_editor_action(
+///     EditorAction::new("Toggle debug overlay", |mut overlay: ResMut<DebugOverlay>| {
+///         overlay.enabled = !overlay.enabled;
+///     })
+///     .with_binding(UserInput::Single(Button::Keyboard(KeyCode::F3)))
+///     .in_menu("View"),
+/// );
+/// ```
+pub struct EditorAction {
+    name: &'static str,
+    handler: BoxedSystem,
+    bindings: Vec<Binding>,
+    menu: Option<Cow<'static, str>>,
+}
+
+impl EditorAction {
+    pub fn new<M>(name: &'static str, handler: impl IntoSystem<(), (), M>) -> Self {
+        EditorAction {
+            name,
+            handler: Box::new(IntoSystem::into_system(handler)),
+            bindings: Vec::new(),
+            menu: None,
+        }
+    }
+
+    /// Adds a default binding. Users can still change it, like the bindings of the builtin actions.
+    pub fn with_binding(mut self, binding: impl Into<Binding>) -> Self {
+        self.bindings.push(binding.into());
+        self
+    }
+
+    /// Also lists the action in a menu of the menu bar, like `Tools`
+    pub fn in_menu(mut self, menu: impl Into<Cow<'static, str>>) -> Self {
+        self.menu = Some(menu.into());
+        self
+    }
+}
+
+struct RegisteredAction {
+    name: &'static str,
+    system: SystemId,
+    default_bindings: Vec<Binding>,
+}
+
+/// The [`EditorAction`]s registered with [`AddEditorAction::add_editor_action`]
+#[derive(Resource, Default)]
+pub struct EditorActions {
+    actions: Vec<RegisteredAction>,
+}
+
+impl EditorActions {
+    /// The builtin actions followed by the [`EditorAction`]s, in the order they were registered
+    pub fn all(&self) -> Vec<Action> {
+        let mut actions = Action::all();
+        actions.extend(
+            self.actions
+                .iter()
+                .map(|action| Action::Custom(action.name)),
+        );
+        actions
+    }
+
+    fn system(&self, name: &str) -> Option<SystemId> {
+        self.actions
+            .iter()
+            .find(|action| action.name == name)
+            .map(|action| action.system)
+    }
+}
+
+/// Extension trait for [`App`] to register [`EditorAction`]s
+pub trait AddEditorAction {
+    fn add_editor_action(&mut self, action: EditorAction) -> &mut Self;
+}
+
+impl AddEditorAction for App {
+    fn add_editor_action(&mut self, action: EditorAction) -> &mut Self {
+        let name = action.name;
+        let actions = self
+            .world_mut()
+            .get_resource_or_insert_with(EditorActions::default);
+        if actions.all().iter().any(|existing| existing.name() == name) {
+            warn!("An editor action named `{name}` is already registered, ignoring the new one");
+            return self;
+        }
+
+        let system = self.world_mut().register_boxed_system(action.handler);
+        let shortcut = action
+            .bindings
+            .first()
+            .map(|binding| binding.input.to_string());
+        self.world_mut()
+            .resource_mut::<EditorActions>()
+            .actions
+            .push(RegisteredAction {
+                name,
+                system,
+                default_bindings: action.bindings,
+            });
+
+        let run = move |world: &mut World| run_action(world, Action::Custom(name));
+        self.add_palette_command(name, run);
+        if let Some(menu) = action.menu {
+            let mut item = MenuItem::new(name, run);
+            if let Some(shortcut) = shortcut {
+                item = item.shortcut(shortcut);
+            }
+            self.add_menu_item(menu, item);
+        }
+        self
+    }
+}
+
+/// Adds the default bindings of newly registered [`EditorAction`]s to the [`EditorControls`]
+pub(crate) fn bind_editor_actions(
+    editor_actions: Res<EditorActions>,
+    mut controls: ResMut<EditorControls>,
+    mut defaults: Option<ResMut<DefaultEditorControls>>,
+) {
+    for registered in &editor_actions.actions {
+        let action = Action::Custom(registered.name);
+        if !controls.actions.contains_key(&action) {
+            controls
+                .actions
+                .insert(action, registered.default_bindings.clone());
         }
+        if let Some(defaults) = defaults.as_mut()
+            && !defaults.0.actions.contains_key(&action)
+        {
+            defaults
+                .0
+                .actions
+                .insert(action, registered.default_bindings.clone());
+        }
+    }
+}
+
+#[cfg(feature = "default_windows")]
+fn set_gizmo_modes(
+    world: &mut World,
+    modes: transform_gizmo_bevy::EnumSet<transform_gizmo_bevy::GizmoMode>,
+) {
+    let mut editor = world.resource_mut::<Editor>();
+    if let Some(gizmo_state) =
+        editor.window_state_mut::<bevy_editor_pls_default_windows::gizmos::GizmoWindow>()
+    {
+        gizmo_state.gizmo_modes = modes;
     }
 }
 
@@ -277,35 +814,61 @@ impl EditorControls {
     /// - `C-Enter`: pause time
     /// - `E`: toggle editor
     /// - `F`: focus on selected entity
+    /// - `C-Z`/`C-S-Z`: undo/redo
+    /// - `C-S-P`: open the command palette
     /// - `T/R/S`: show translate/rotate/scale gizmo
+    /// - `F10`/`F11`: step 1/N frames
+    /// - `F9`: run `FixedUpdate` once
+    /// - `C-Minus`/`C-Equal`: decrease/increase time speed
     pub fn default_bindings() -> Self {
         let mut controls = EditorControls::default();
 
         controls.insert(
             Action::PauseUnpauseTime,
-            Binding {
-                input: UserInput::Chord(vec![
-                    Button::Keyboard(KeyCode::ControlLeft),
-                    Button::Keyboard(KeyCode::Enter),
-                ]),
-                conditions: vec![BindingCondition::ListeningForText(false)],
-            },
+            Binding::new(UserInput::Chord(vec![
+                Button::Keyboard(KeyCode::ControlLeft),
+                Button::Keyboard(KeyCode::Enter),
+            ]))
+            .with_conditions([BindingCondition::ListeningForText(false)]),
         );
 
         controls.insert(
             Action::PlayPauseEditor,
-            Binding {
-                input: UserInput::Single(Button::Keyboard(KeyCode::KeyE)),
-                conditions: vec![BindingCondition::ListeningForText(false)],
-            },
+            Binding::new(UserInput::Single(Button::Keyboard(KeyCode::KeyE)))
+                .with_conditions([BindingCondition::ListeningForText(false)]),
         );
 
         controls.insert(
             Action::FocusSelected,
-            Binding {
-                input: UserInput::Single(Button::Keyboard(KeyCode::KeyF)),
-                conditions: vec![BindingCondition::EditorActive(true)],
-            },
+            Binding::new(UserInput::Single(Button::Keyboard(KeyCode::KeyF)))
+                .with_conditions([BindingCondition::EditorActive(true)]),
+        );
+
+        controls.insert(
+            Action::Undo,
+            Binding::new(UserInput::Chord(vec![
+                Button::Keyboard(KeyCode::ControlLeft),
+                Button::Keyboard(KeyCode::KeyZ),
+            ]))
+            .with_conditions([BindingCondition::ListeningForText(false)]),
+        );
+        controls.insert(
+            Action::Redo,
+            Binding::new(UserInput::Chord(vec![
+                Button::Keyboard(KeyCode::ControlLeft),
+                Button::Keyboard(KeyCode::ShiftLeft),
+                Button::Keyboard(KeyCode::KeyZ),
+            ]))
+            .with_conditions([BindingCondition::ListeningForText(false)]),
+        );
+
+        controls.insert(
+            Action::OpenCommandPalette,
+            Binding::new(UserInput::Chord(vec![
+                Button::Keyboard(KeyCode::ControlLeft),
+                Button::Keyboard(KeyCode::ShiftLeft),
+                Button::Keyboard(KeyCode::KeyP),
+            ])),
         );
 
         #[cfg(feature = "default_windows")]
@@ -322,21 +885,132 @@ impl EditorControls {
                 Action::SetGizmoModeScale,
                 UserInput::Single(Button::Keyboard(KeyCode::KeyS)).into(),
             );
+
+            let no_text_input = || [BindingCondition::ListeningForText(false)];
+            for (action, key) in [
+                (Action::StepFrame, KeyCode::F10),
+                (Action::StepFrames, KeyCode::F11),
+                (Action::StepFixedUpdate, KeyCode::F9),
+            ] {
+                controls.insert(
+                    action,
+                    Binding::new(UserInput::Single(Button::Keyboard(key)))
+                        .with_conditions(no_text_input()),
+                );
+            }
+            for (action, key) in [
+                (Action::DecreaseTimeSpeed, KeyCode::Minus),
+                (Action::IncreaseTimeSpeed, KeyCode::Equal),
+            ] {
+                controls.insert(
+                    action,
+                    Binding::new(UserInput::Chord(vec![
+                        Button::Keyboard(KeyCode::ControlLeft),
+                        Button::Keyboard(key),
+                    ]))
+                    .with_conditions(no_text_input()),
+                );
+            }
         }
 
         controls
     }
 }
 
+/// Uses the key syntax of the [`Keymap`], e.g. `Ctrl`, `A`, `1`, `F10`, `MouseLeft` or `GamepadSouth`
 impl std::fmt::Display for Button {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Button::Keyboard(key) => write!(f, "{:?}", key),
-            Button::Mouse(mouse) => write!(f, "{:?}", mouse),
+            Button::Keyboard(KeyCode::ControlLeft) => write!(f, "Ctrl"),
+            Button::Keyboard(KeyCode::ShiftLeft) => write!(f, "Shift"),
+            Button::Keyboard(KeyCode::AltLeft) => write!(f, "Alt"),
+            Button::Keyboard(KeyCode::SuperLeft) => write!(f, "Super"),
+            Button::Keyboard(key) => {
+                let name = format!("{:?}", key);
+                let short = name
+                    .strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .filter(|short| short.len() == 1);
+                f.write_str(short.unwrap_or(name.as_str()))
+            }
+            Button::Mouse(MouseButton::Other(button)) => write!(f, "Mouse{}", button),
+            Button::Mouse(mouse) => write!(f, "Mouse{:?}", mouse),
+            Button::Gamepad(GamepadButton::Other(button)) => write!(f, "Gamepad{}", button),
+            Button::Gamepad(button) => write!(f, "Gamepad{:?}", button),
+        }
+    }
+}
+
+impl FromStr for Button {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lowercase = s.to_ascii_lowercase();
+        let alias = match lowercase.as_str() {
+            "ctrl" | "control" => Some(KeyCode::ControlLeft),
+            "shift" => Some(KeyCode::ShiftLeft),
+            "alt" | "option" => Some(KeyCode::AltLeft),
+            "super" | "cmd" | "command" | "meta" | "win" => Some(KeyCode::SuperLeft),
+            "esc" => Some(KeyCode::Escape),
+            "up" => Some(KeyCode::ArrowUp),
+            "down" => Some(KeyCode::ArrowDown),
+            "left" => Some(KeyCode::ArrowLeft),
+            "right" => Some(KeyCode::ArrowRight),
+            _ => None,
+        };
+        if let Some(key) = alias {
+            return Ok(Button::Keyboard(key));
         }
+
+        if let Some(mouse) = lowercase.strip_prefix("mouse") {
+            let button = match mouse {
+                "left" => MouseButton::Left,
+                "right" => MouseButton::Right,
+                "middle" => MouseButton::Middle,
+                "back" => MouseButton::Back,
+                "forward" => MouseButton::Forward,
+                other => other.parse().map(MouseButton::Other).map_err(|_| {
+                    format!("unknown mouse button `{s}`, expected MouseLeft, MouseRight, MouseMiddle, MouseBack, MouseForward or Mouse<number>")
+                })?,
+            };
+            return Ok(Button::Mouse(button));
+        }
+
+        if let Some(gamepad) = lowercase.strip_prefix("gamepad") {
+            let button = GamepadButton::all()
+                .into_iter()
+                .find(|button| format!("{button:?}").eq_ignore_ascii_case(gamepad));
+            return match button {
+                Some(button) => Ok(Button::Gamepad(button)),
+                None => gamepad.parse().map(|button| Button::Gamepad(GamepadButton::Other(button))).map_err(|_| {
+                    format!("unknown gamepad button `{s}`, expected e.g. GamepadSouth, GamepadStart, GamepadDPadUp or Gamepad<number>")
+                }),
+            };
+        }
+
+        let name = match s.chars().collect::<Vec<_>>().as_slice() {
+            [c] if c.is_ascii_alphabetic() => format!("Key{}", c.to_ascii_uppercase()),
+            [c] if c.is_ascii_digit() => format!("Digit{c}"),
+            _ => s.to_owned(),
+        };
+        key_code_from_name(&name)
+            .map(Button::Keyboard)
+            .ok_or_else(|| format!("unknown key `{s}`"))
     }
 }
 
+/// Looks up the unit variant of [`KeyCode`] called `name`, ignoring case
+fn key_code_from_name(name: &str) -> Option<KeyCode> {
+    let TypeInfo::Enum(info) = KeyCode::type_info() else {
+        return None;
+    };
+    let variant = info
+        .variant_names()
+        .iter()
+        .find(|variant| variant.eq_ignore_ascii_case(name))?;
+    KeyCode::from_reflect(&DynamicEnum::new(*variant, DynamicVariant::Unit))
+}
+
 impl std::fmt::Display for UserInput {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -351,14 +1025,130 @@ impl std::fmt::Display for UserInput {
                 }
 
                 for remaining in iter {
-                    write!(f, " + {}", remaining)?;
+                    write!(f, "+{}", remaining)?;
                 }
             }
+            UserInput::Hold(button, duration) => {
+                write!(f, "hold {} for {}s", button, duration.as_secs_f32())?;
+            }
+            UserInput::DoubleTap(button) => {
+                write!(f, "double tap {}", button)?;
+            }
+            UserInput::Sequence(sequence) => {
+                let steps: Vec<_> = sequence.iter().map(Button::to_string).collect();
+                write!(f, "{}", steps.join(" then "))?;
+            }
         }
         Ok(())
     }
 }
 
+/// Parses inputs like
+/// - `Ctrl+Shift+Z`, where every button but the last is held down
+/// - `hold F for 0.5s` or `hold GamepadStart for 500ms`
+/// - `double tap F`
+/// - `G then X`
+impl FromStr for UserInput {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        // keywords ignore case, the lowercase string has the same byte offsets as `s`
+        let lowercase = s.to_ascii_lowercase();
+        if lowercase.starts_with("hold ") {
+            let (button, duration) = split_once_ignore_case(&s["hold ".len()..], " for ")
+                .ok_or_else(|| format!("expected `hold <button> for <duration>`, got `{s}`"))?;
+            return Ok(UserInput::Hold(
+                button.trim().parse()?,
+                parse_duration(duration.trim())?,
+            ));
+        }
+        if lowercase.starts_with("double tap ") {
+            return Ok(UserInput::DoubleTap(
+                s["double tap ".len()..].trim().parse()?,
+            ));
+        }
+        if lowercase.contains(" then ") {
+            let mut sequence = Vec::new();
+            let mut rest = s;
+            while let Some((button, next)) = split_once_ignore_case(rest, " then ") {
+                sequence.push(button.trim().parse()?);
+                rest = next;
+            }
+            sequence.push(rest.trim().parse()?);
+            return Ok(UserInput::Sequence(sequence));
+        }
+
+        let mut buttons = s
+            .split('+')
+            .map(|button| match button.trim() {
+                "" => Err(format!("empty key in `{s}`")),
+                button => button.parse::<Button>(),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(match buttons.len() {
+            1 => UserInput::Single(buttons.remove(0)),
+            _ => UserInput::Chord(buttons),
+        })
+    }
+}
+
+/// Like [`str::split_once`], but matches the lowercase ASCII `separator` ignoring case
+fn split_once_ignore_case<'a>(s: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    let index = s.to_ascii_lowercase().find(separator)?;
+    Some((&s[..index], &s[index + separator.len()..]))
+}
+
+/// Parses durations like `0.5s` or `500ms`
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration `{s}`, expected e.g. `0.5s` or `500ms`");
+    let seconds = match s.strip_suffix("ms") {
+        Some(millis) => millis.trim().parse::<f32>().map_err(|_| invalid())? / 1000.0,
+        None => s
+            .strip_suffix('s')
+            .ok_or_else(invalid)?
+            .trim()
+            .parse::<f32>()
+            .map_err(|_| invalid())?,
+    };
+    Duration::try_from_secs_f32(seconds).map_err(|_| invalid())
+}
+
+impl BindingCondition {
+    /// Name of the condition in the [`Keymap`], like `in_viewport` or `!listening_for_text`
+    pub fn keymap_name(&self) -> String {
+        let (name, value) = match *self {
+            BindingCondition::InViewport(value) => ("in_viewport", value),
+            BindingCondition::EditorActive(value) => ("editor_active", value),
+            BindingCondition::ListeningForText(value) => ("listening_for_text", value),
+        };
+        if value {
+            name.to_owned()
+        } else {
+            format!("!{name}")
+        }
+    }
+}
+
+impl FromStr for BindingCondition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = match s.trim().strip_prefix('!') {
+            Some(negated) => (negated.trim(), false),
+            None => (s.trim(), true),
+        };
+        match name {
+            "in_viewport" => Ok(BindingCondition::InViewport(value)),
+            "editor_active" => Ok(BindingCondition::EditorActive(value)),
+            "listening_for_text" => Ok(BindingCondition::ListeningForText(value)),
+            _ => Err(format!(
+                "unknown condition `{s}`, expected `in_viewport`, `editor_active` or `listening_for_text`, optionally prefixed with `!`"
+            )),
+        }
+    }
+}
+
 impl std::fmt::Display for Binding {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.input)?;
@@ -376,29 +1166,899 @@ impl std::fmt::Display for Binding {
     }
 }
 
+/// The bindings in [`EditorPersistence::KEYMAP_FILE`], by the name of their [`Action`]:
+/// ```ron
+/// {
+///     "Undo": [(input: "Ctrl+Z", when: ["!listening_for_text"])],
+///     "FocusSelected": [(input: "F"), (input: "MouseMiddle", when: ["in_viewport"], allow_extra_modifiers: true)],
+///     "StepFrame": [],
+/// }
+/// ```
+/// Actions listed in the keymap replace all of their bindings, the others keep the ones of [`EditorControls`].
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(transparent)]
+pub struct Keymap(pub BTreeMap<String, Vec<KeymapBinding>>);
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct KeymapBinding {
+    /// The [`UserInput`], like `Ctrl+Enter`
+    pub input: String,
+    /// The [`BindingCondition`]s, like `in_viewport` or `!listening_for_text`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub when: Vec<String>,
+    /// See [`Binding::allow_extra_modifiers`]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_extra_modifiers: bool,
+}
+
+/// Two bindings of different actions which can be triggered by the same press, see [`EditorControls::conflicts`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BindingConflict {
+    /// The action and the index of its binding
+    pub first: (Action, usize),
+    pub second: (Action, usize),
+}
+
+impl EditorControls {
+    /// Pairs of bindings of different actions which share an input,
+    /// under [`BindingCondition`]s which don't exclude each other
+    pub fn conflicts(&self) -> Vec<BindingConflict> {
+        let bindings: Vec<_> = self
+            .keymap_actions()
+            .into_iter()
+            .flat_map(|action| {
+                self.get(&action)
+                    .iter()
+                    .enumerate()
+                    .map(move |(index, binding)| ((action, index), binding))
+            })
+            .collect();
+
+        let mut conflicts = Vec::new();
+        for (i, (first, binding)) in bindings.iter().enumerate() {
+            for (second, other) in &bindings[i + 1..] {
+                if first.0 != second.0 && binding.overlaps(other) {
+                    conflicts.push(BindingConflict {
+                        first: *first,
+                        second: *second,
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+/// An entry of the [`Keymap`] which could not be applied
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeymapError {
+    pub action: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`: {}", self.action, self.message)
+    }
+}
+
+impl Keymap {
+    pub fn load(path: &Path) -> Result<Self, LayoutError> {
+        layout::read_ron(path)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), LayoutError> {
+        layout::write_ron(self, path)
+    }
+}
+
+impl KeymapBinding {
+    fn parse(&self) -> Result<Binding, String> {
+        let input = self.input.parse()?;
+        let conditions = self
+            .when
+            .iter()
+            .map(|condition| condition.parse())
+            .collect::<Result<Vec<_>, _>>()?;
+        let binding = Binding::new(input).with_conditions(conditions);
+        Ok(match self.allow_extra_modifiers {
+            true => binding.allow_extra_modifiers(),
+            false => binding,
+        })
+    }
+}
+
+impl From<&Binding> for KeymapBinding {
+    fn from(binding: &Binding) -> Self {
+        KeymapBinding {
+            input: binding.input.to_string(),
+            when: binding
+                .conditions
+                .iter()
+                .map(BindingCondition::keymap_name)
+                .collect(),
+            allow_extra_modifiers: binding.allow_extra_modifiers,
+        }
+    }
+}
+
+impl EditorControls {
+    /// The bindings of every [`Action`], including the unbound ones
+    pub fn to_keymap(&self) -> Keymap {
+        let actions = self
+            .keymap_actions()
+            .into_iter()
+            .map(|action| {
+                let bindings = self.get(&action).iter().map(KeymapBinding::from).collect();
+                (action.name(), bindings)
+            })
+            .collect();
+        Keymap(actions)
+    }
+
+    /// The builtin actions and the [`EditorAction`]s known to these controls
+    fn keymap_actions(&self) -> Vec<Action> {
+        let mut custom: Vec<_> = self
+            .actions
+            .keys()
+            .filter(|action| matches!(action, Action::Custom(_)))
+            .copied()
+            .collect();
+        custom.sort_by_key(Action::name);
+
+        let mut actions = Action::all();
+        actions.extend(custom);
+        actions
+    }
+
+    /// Replaces the bindings of the actions listed in `keymap`.
+    /// Invalid entries are skipped and returned, the valid bindings of an action are still applied.
+    pub fn apply_keymap(&mut self, keymap: &Keymap) -> Vec<KeymapError> {
+        let mut errors = Vec::new();
+        for (name, entries) in &keymap.0 {
+            let actions = self.keymap_actions();
+            let Some(action) = actions
+                .iter()
+                .copied()
+                .find(|action| action.name() == *name)
+            else {
+                let actions: Vec<_> = actions.iter().map(Action::name).collect();
+                errors.push(KeymapError {
+                    action: name.clone(),
+                    message: format!("unknown action, expected one of {}", actions.join(", ")),
+                });
+                continue;
+            };
+
+            let mut bindings = Vec::with_capacity(entries.len());
+            for entry in entries {
+                match entry.parse() {
+                    Ok(binding) => bindings.push(binding),
+                    Err(message) => errors.push(KeymapError {
+                        action: name.clone(),
+                        message,
+                    }),
+                }
+            }
+            self.actions.insert(action, bindings);
+        }
+        errors
+    }
+}
+
+/// How often [`hot_reload_keymap`] checks whether the keymap file changed
+const KEYMAP_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The controls before the keymap was applied, used for actions missing from the keymap and by "Reset to defaults"
+#[derive(Resource)]
+pub(crate) struct DefaultEditorControls(EditorControls);
+
+#[derive(Default)]
+pub(crate) struct KeymapWatcher {
+    last_modified: Option<SystemTime>,
+    last_check: Option<Duration>,
+}
+
+/// Applies [`EditorPersistence::KEYMAP_FILE`] on startup and whenever it changes.
+/// If there is no keymap yet, the current bindings are written to it so they can be edited.
+pub(crate) fn hot_reload_keymap(
+    mut commands: Commands,
+    mut watcher: Local<KeymapWatcher>,
+    time: Res<Time<Real>>,
+    persistence: Res<EditorPersistence>,
+    defaults: Option<Res<DefaultEditorControls>>,
+    mut controls: ResMut<EditorControls>,
+    mut notifications: ResMut<Notifications>,
+) {
+    let now = time.elapsed();
+    if watcher
+        .last_check
+        .is_some_and(|last_check| now - last_check < KEYMAP_POLL_INTERVAL)
+    {
+        return;
+    }
+    let first_run = watcher.last_check.is_none();
+    watcher.last_check = Some(now);
+
+    let base = match defaults {
+        Some(defaults) => defaults.0.clone(),
+        None => {
+            commands.insert_resource(DefaultEditorControls(controls.clone()));
+            controls.clone()
+        }
+    };
+
+    let Some(path) = persistence.user_path(EditorPersistence::KEYMAP_FILE) else {
+        return;
+    };
+
+    let modified = |path: &Path| {
+        std::fs::metadata(path)
+            .and_then(|meta| meta.modified())
+            .ok()
+    };
+    if !path.exists() {
+        if first_run {
+            match base.to_keymap().save(&path) {
+                Ok(()) => watcher.last_modified = modified(&path),
+                Err(error) => warn!("Could not write keymap to {}: {error}", path.display()),
+            }
+        } else if watcher.last_modified.take().is_some() {
+            *controls = base;
+            notifications.info("Keymap was removed, using the default bindings");
+        }
+        return;
+    }
+
+    let last_modified = modified(&path);
+    if last_modified == watcher.last_modified {
+        return;
+    }
+    watcher.last_modified = last_modified;
+
+    let keymap = match Keymap::load(&path) {
+        Ok(keymap) => keymap,
+        Err(error) => {
+            notifications.error(format!(
+                "Could not load keymap from {}: {error}",
+                path.display()
+            ));
+            return;
+        }
+    };
+
+    let mut new_controls = base;
+    let errors = new_controls.apply_keymap(&keymap);
+    for error in &errors {
+        notifications.warning(format!("Invalid keymap entry {error}"));
+    }
+    // saving from the `ControlsWindow` also touches the file, but doesn't change anything
+    if *controls != new_controls {
+        *controls = new_controls;
+        if !first_run && errors.is_empty() {
+            notifications.success("Reloaded keymap");
+        }
+    }
+}
+
+/// Writes the current [`EditorControls`] to [`EditorPersistence::KEYMAP_FILE`]
+pub fn save_keymap(world: &mut World) {
+    let Some(path) = world
+        .resource::<EditorPersistence>()
+        .user_path(EditorPersistence::KEYMAP_FILE)
+    else {
+        return;
+    };
+    let keymap = world.resource::<EditorControls>().to_keymap();
+    if let Err(error) = keymap.save(&path) {
+        world.resource_mut::<Notifications>().error(format!(
+            "Could not save keymap to {}: {error}",
+            path.display()
+        ));
+    }
+}
+
 pub struct ControlsWindow;
 
+#[derive(Default)]
+pub struct ControlsWindowState {
+    /// The binding waiting for an input to be pressed, `None` as the index adds a new binding
+    capture: Option<(Action, Option<usize>)>,
+}
+
+impl ControlsWindowState {
+    /// While capturing, pressed inputs are recorded as a binding instead of running actions
+    pub fn is_capturing(&self) -> bool {
+        self.capture.is_some()
+    }
+}
+
 impl EditorWindow for ControlsWindow {
-    type State = ();
+    type State = ControlsWindowState;
     const NAME: &'static str = "Controls";
 
-    fn ui(
-        world: &mut World,
-        _: bevy_editor_pls_core::editor_window::EditorWindowContext,
-        ui: &mut egui::Ui,
-    ) {
-        let controls = world.get_resource::<EditorControls>().unwrap();
+    fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let state = cx.state_mut::<ControlsWindow>().unwrap();
+        let mut controls = world.resource::<EditorControls>().clone();
+        let actions = world
+            .get_resource::<EditorActions>()
+            .map_or_else(Action::all, EditorActions::all);
+        let mut changed = false;
 
-        for action in &[
-            Action::PlayPauseEditor,
-            Action::PauseUnpauseTime,
-            Action::FocusSelected,
+        if let Some((action, index)) = state.capture
+            && let Some(input) = captured_input(world)
+        {
+            let bindings = controls.actions.entry(action).or_default();
+            match index.and_then(|index| bindings.get_mut(index)) {
+                Some(binding) => binding.input = input,
+                None => bindings.push(input.into()),
+            }
+            state.capture = None;
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Reset to defaults").clicked() {
+                controls = world
+                    .get_resource::<DefaultEditorControls>()
+                    .map_or_else(EditorControls::default_bindings, |defaults| {
+                        defaults.0.clone()
+                    });
+                state.capture = None;
+                changed = true;
+            }
+            match world
+                .resource::<EditorPersistence>()
+                .user_path(EditorPersistence::KEYMAP_FILE)
+            {
+                Some(path) => ui.weak(format!("Saved to {}", path.display())),
+                None => ui.weak("Persistence is disabled, changes are not saved"),
+            };
+        });
+        ui.separator();
+
+        let conflicts = controls.conflicts();
+        let describe = |(action, index): (Action, usize)| {
+            format!("{action} ({})", controls.get(&action)[index].input)
+        };
+        let mut conflicting: HashMap<(Action, usize), Vec<String>> = HashMap::default();
+        if !conflicts.is_empty() {
+            let warn = ui.visuals().warn_fg_color;
+            ui.colored_label(warn, "⚠ Some bindings can trigger at the same time:");
+            for conflict in &conflicts {
+                let (first, second) = (describe(conflict.first), describe(conflict.second));
+                ui.colored_label(warn, format!("    {first} and {second}"));
+                conflicting.entry(conflict.first).or_default().push(second);
+                conflicting.entry(conflict.second).or_default().push(first);
+            }
+            ui.separator();
+        }
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for action in actions {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(action.to_string()).strong());
+                    if ui
+                        .small_button("➕")
+                        .on_hover_text("Add a binding by pressing it")
+                        .clicked()
+                    {
+                        state.capture = Some((action, None));
+                    }
+                });
+
+                let bindings = controls.actions.entry(action).or_default();
+                let mut remove = None;
+                for (index, binding) in bindings.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.add_space(12.0);
+                        if state.capture == Some((action, Some(index))) {
+                            capture_ui(ui, &mut state.capture);
+                        } else {
+                            ui.monospace(binding.input.to_string());
+                            if let Some(others) = conflicting.get(&(action, index)) {
+                                ui.colored_label(ui.visuals().warn_fg_color, "⚠")
+                                    .on_hover_text(format!(
+                                        "Can trigger together with {}",
+                                        others.join(", ")
+                                    ));
+                            }
+                            if ui
+                                .small_button("⌨")
+                                .on_hover_text("Press a new input")
+                                .clicked()
+                            {
+                                state.capture = Some((action, Some(index)));
+                            }
+                        }
+                        if ui.small_button("🗑").on_hover_text("Remove").clicked() {
+                            remove = Some(index);
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.add_space(24.0);
+                        changed |= conditions_ui(ui, (action, index), &mut binding.conditions);
+                        changed |= ui
+                            .checkbox(&mut binding.allow_extra_modifiers, "Extra modifiers")
+                            .on_hover_text("Also trigger while other modifier keys are held")
+                            .changed();
+                    });
+                }
+                if let Some(index) = remove {
+                    bindings.remove(index);
+                    state.capture = None;
+                    changed = true;
+                }
+                if state.capture == Some((action, None)) {
+                    ui.horizontal(|ui| {
+                        ui.add_space(12.0);
+                        capture_ui(ui, &mut state.capture);
+                    });
+                }
+                ui.add_space(4.0);
+            }
+        });
+
+        if changed {
+            *world.resource_mut::<EditorControls>() = controls;
+            save_keymap(world);
+        }
+    }
+}
+
+fn capture_ui(ui: &mut egui::Ui, capture: &mut Option<(Action, Option<usize>)>) {
+    ui.label(
+        egui::RichText::new(
+            "Press a key, a gamepad button or a mouse button other than the left one…",
+        )
+        .italics(),
+    );
+    if ui.small_button("Cancel").clicked() {
+        *capture = None;
+    }
+}
+
+/// The first input pressed this frame, together with the modifiers held down.
+/// Holds, double taps and sequences can be set up in the [`Keymap`].
+/// The left mouse button is ignored so that the ui stays usable.
+fn captured_input(world: &mut World) -> Option<UserInput> {
+    let gamepad_button = world
+        .query::<&Gamepad>()
+        .iter(world)
+        .find_map(|gamepad| gamepad.get_just_pressed().next().copied());
+    let keyboard_input = world.resource::<ButtonInput<KeyCode>>();
+    let mouse_input = world.resource::<ButtonInput<MouseButton>>();
+
+    let pressed = keyboard_input
+        .get_just_pressed()
+        .find(|key| !MODIFIERS.contains(*key))
+        .map(|key| Button::Keyboard(*key))
+        .or_else(|| {
+            mouse_input
+                .get_just_pressed()
+                .find(|button| **button != MouseButton::Left)
+                .map(|button| Button::Mouse(*button))
+        })
+        .or(gamepad_button.map(Button::Gamepad))?;
+
+    let mut buttons: Vec<Button> = MODIFIERS
+        .into_iter()
+        .filter(|modifier| keyboard_input.pressed(*modifier))
+        .map(Button::Keyboard)
+        .collect();
+    buttons.push(pressed);
+    Some(match buttons.len() {
+        1 => UserInput::Single(buttons.remove(0)),
+        _ => UserInput::Chord(buttons),
+    })
+}
+
+/// Label and constructor of a kind of [`BindingCondition`]
+type ConditionKind = (&'static str, fn(bool) -> BindingCondition);
+
+/// One combo box per kind of [`BindingCondition`], returns whether the conditions were changed
+fn conditions_ui(
+    ui: &mut egui::Ui,
+    id: (Action, usize),
+    conditions: &mut Vec<BindingCondition>,
+) -> bool {
+    let kinds: [ConditionKind; 3] = [
+        ("Mouse in viewport", BindingCondition::InViewport),
+        ("Editor active", BindingCondition::EditorActive),
+        ("Listening for text", BindingCondition::ListeningForText),
+    ];
+
+    let mut changed = false;
+    for (name, condition) in kinds {
+        let position = conditions.iter().position(|existing| {
+            std::mem::discriminant(existing) == std::mem::discriminant(&condition(true))
+        });
+        let current = position.map(|position| conditions[position] == condition(true));
+
+        let mut selected = current;
+        let text = |value: Option<bool>| match value {
+            None => "any",
+            Some(true) => "yes",
+            Some(false) => "no",
+        };
+        ui.label(name);
+        egui::ComboBox::from_id_salt((id, name))
+            .width(48.0)
+            .selected_text(text(selected))
+            .show_ui(ui, |ui| {
+                for value in [None, Some(true), Some(false)] {
+                    ui.selectable_value(&mut selected, value, text(value));
+                }
+            });
+
+        if selected != current {
+            if let Some(position) = position {
+                conditions.remove(position);
+            }
+            if let Some(value) = selected {
+                conditions.push(condition(value));
+            }
+            changed = true;
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keymap_entry(input: &str, when: &[&str]) -> KeymapBinding {
+        KeymapBinding {
+            input: input.to_owned(),
+            when: when.iter().map(|condition| condition.to_string()).collect(),
+            allow_extra_modifiers: false,
+        }
+    }
+
+    #[test]
+    fn buttons_round_trip_through_their_keymap_names() {
+        let buttons = [
+            Button::Keyboard(KeyCode::KeyA),
+            Button::Keyboard(KeyCode::Digit1),
+            Button::Keyboard(KeyCode::F10),
+            Button::Keyboard(KeyCode::Enter),
+            Button::Keyboard(KeyCode::ControlLeft),
+            Button::Keyboard(KeyCode::ShiftLeft),
+            Button::Keyboard(KeyCode::AltLeft),
+            Button::Keyboard(KeyCode::SuperLeft),
+            Button::Keyboard(KeyCode::ControlRight),
+            Button::Mouse(MouseButton::Left),
+            Button::Mouse(MouseButton::Middle),
+            Button::Mouse(MouseButton::Other(5)),
+            Button::Gamepad(GamepadButton::South),
+        ];
+        for button in buttons {
+            assert_eq!(button.to_string().parse(), Ok(button));
+        }
+    }
+
+    #[test]
+    fn short_key_names() {
+        assert_eq!(Button::Keyboard(KeyCode::KeyA).to_string(), "A");
+        assert_eq!(Button::Keyboard(KeyCode::Digit1).to_string(), "1");
+        assert_eq!(Button::Keyboard(KeyCode::ControlLeft).to_string(), "Ctrl");
+        assert_eq!("a".parse(), Ok(Button::Keyboard(KeyCode::KeyA)));
+        assert_eq!("esc".parse(), Ok(Button::Keyboard(KeyCode::Escape)));
+        assert_eq!("mouseleft".parse(), Ok(Button::Mouse(MouseButton::Left)));
+    }
+
+    #[test]
+    fn chords_round_trip() {
+        let input = UserInput::Chord(vec![
+            Button::Keyboard(KeyCode::ControlLeft),
+            Button::Keyboard(KeyCode::ShiftLeft),
+            Button::Keyboard(KeyCode::KeyZ),
+        ]);
+        assert_eq!(input.to_string(), "Ctrl+Shift+Z");
+        assert_eq!(input.to_string().parse(), Ok(input));
+    }
+
+    #[test]
+    fn conditions_round_trip() {
+        for condition in [
+            BindingCondition::InViewport(true),
+            BindingCondition::EditorActive(false),
+            BindingCondition::ListeningForText(false),
         ] {
-            ui.label(egui::RichText::new(action.to_string()).strong());
-            let bindings = controls.get(action);
-            for binding in bindings {
-                ui.add(egui::Label::new(format!("{}", binding)).extend());
+            assert_eq!(condition.keymap_name().parse(), Ok(condition));
+        }
+    }
+
+    #[test]
+    fn unknown_keys_are_reported() {
+        assert_eq!("Foo".parse::<Button>(), Err("unknown key `Foo`".to_owned()));
+        assert_eq!(
+            "Ctrl+".parse::<UserInput>(),
+            Err("empty key in `Ctrl+`".to_owned())
+        );
+        assert!("MouseSide"
+            .parse::<Button>()
+            .unwrap_err()
+            .starts_with("unknown mouse button `MouseSide`"));
+    }
+
+    #[test]
+    fn unknown_conditions_are_reported() {
+        let error = "hovered".parse::<BindingCondition>().unwrap_err();
+        assert!(error.starts_with("unknown condition `hovered`"), "{error}");
+    }
+
+    #[test]
+    fn unknown_actions_are_reported() {
+        let mut controls = EditorControls::default_bindings();
+        let keymap = Keymap(BTreeMap::from([(
+            "Jump".to_owned(),
+            vec![keymap_entry("Space", &[])],
+        )]));
+
+        let errors = controls.apply_keymap(&keymap);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].action, "Jump");
+        assert!(errors[0]
+            .message
+            .starts_with("unknown action, expected one of"));
+        assert_eq!(controls, EditorControls::default_bindings());
+    }
+
+    #[test]
+    fn invalid_bindings_are_skipped() {
+        let mut controls = EditorControls::default_bindings();
+        let keymap = Keymap(BTreeMap::from([(
+            "Undo".to_owned(),
+            vec![
+                keymap_entry("Ctrl+Foo", &[]),
+                keymap_entry("Ctrl+U", &["!listening_for_text"]),
+            ],
+        )]));
+
+        let errors = controls.apply_keymap(&keymap);
+        assert_eq!(
+            errors,
+            vec![KeymapError {
+                action: "Undo".to_owned(),
+                message: "unknown key `Foo`".to_owned(),
+            }]
+        );
+        assert_eq!(controls.get(&Action::Undo).len(), 1);
+        assert_eq!(controls.get(&Action::Undo)[0].input.to_string(), "Ctrl+U");
+    }
+
+    #[test]
+    fn unlisted_actions_keep_their_default_bindings() {
+        let defaults = EditorControls::default_bindings();
+        let mut controls = defaults.clone();
+        let keymap = Keymap(BTreeMap::from([
+            ("Undo".to_owned(), vec![keymap_entry("Ctrl+U", &[])]),
+            ("StepFrame".to_owned(), Vec::new()),
+        ]));
+
+        assert!(controls.apply_keymap(&keymap).is_empty());
+        assert!(controls.get(&Action::StepFrame).is_empty());
+        assert_ne!(controls.get(&Action::Undo), defaults.get(&Action::Undo));
+        for action in Action::all() {
+            if !matches!(action, Action::Undo | Action::StepFrame) {
+                assert_eq!(controls.get(&action), defaults.get(&action), "{action}");
             }
         }
     }
+
+    #[test]
+    fn keymaps_round_trip() {
+        let defaults = EditorControls::default_bindings();
+        let mut controls = EditorControls::default();
+        assert!(controls.apply_keymap(&defaults.to_keymap()).is_empty());
+        assert_eq!(controls, defaults);
+    }
+
+    /// Simulates frames of keyboard input, like `editor_controls_system`
+    struct Frames {
+        controls: EditorControls,
+        keyboard_input: ButtonInput<KeyCode>,
+        mouse_input: ButtonInput<MouseButton>,
+        history: InputHistory,
+    }
+
+    impl Frames {
+        fn new() -> Self {
+            Frames {
+                controls: EditorControls::default(),
+                keyboard_input: ButtonInput::default(),
+                mouse_input: ButtonInput::default(),
+                history: InputHistory::default(),
+            }
+        }
+
+        /// Runs a frame `millis` after the previous one and returns whether `input` triggered in it
+        fn frame(&mut self, millis: u64, input: &UserInput) -> bool {
+            self.run(millis, |state, controls| {
+                input.just_pressed(state, controls)
+            })
+        }
+
+        /// Like [`Frames::frame`], but also checks the conditions and modifiers of `binding`
+        fn binding_frame(&mut self, millis: u64, binding: &Binding) -> bool {
+            let editor = Editor::new(Entity::PLACEHOLDER, false);
+            self.run(millis, |state, controls| {
+                binding.just_pressed(state, controls, &editor)
+            })
+        }
+
+        fn run(
+            &mut self,
+            millis: u64,
+            triggered: impl FnOnce(&InputState, &EditorControls) -> bool,
+        ) -> bool {
+            let now = self.history.last_update + Duration::from_millis(millis);
+            let previous = std::mem::replace(&mut self.history.last_update, now);
+            for key in self.keyboard_input.get_just_pressed() {
+                self.history.record(Button::Keyboard(*key), now);
+            }
+
+            let state = InputState {
+                keyboard_input: &self.keyboard_input,
+                mouse_input: &self.mouse_input,
+                gamepads: Vec::new(),
+                history: &self.history,
+                now,
+                previous,
+            };
+            let triggered = triggered(&state, &self.controls);
+            self.keyboard_input.clear();
+            triggered
+        }
+
+        /// Presses and releases `key` in a frame `millis` after the previous one
+        fn tap(&mut self, millis: u64, key: KeyCode, input: &UserInput) -> bool {
+            self.keyboard_input.press(key);
+            let triggered = self.frame(millis, input);
+            self.keyboard_input.release(key);
+            triggered
+        }
+    }
+
+    #[test]
+    fn double_taps_trigger_within_the_timeout() {
+        let input = UserInput::DoubleTap(Button::Keyboard(KeyCode::KeyF));
+        let mut frames = Frames::new();
+        assert!(!frames.tap(100, KeyCode::KeyF, &input));
+        assert!(frames.tap(200, KeyCode::KeyF, &input));
+
+        let mut frames = Frames::new();
+        assert!(!frames.tap(100, KeyCode::KeyF, &input));
+        assert!(!frames.tap(400, KeyCode::KeyF, &input));
+    }
+
+    #[test]
+    fn triple_taps_trigger_once() {
+        let input = UserInput::DoubleTap(Button::Keyboard(KeyCode::KeyF));
+        let mut frames = Frames::new();
+        let triggered: Vec<_> = (0..4)
+            .map(|_| frames.tap(100, KeyCode::KeyF, &input))
+            .collect();
+        assert_eq!(triggered, [false, true, false, true]);
+    }
+
+    #[test]
+    fn sequences_time_out() {
+        let input = UserInput::Sequence(vec![
+            Button::Keyboard(KeyCode::KeyG),
+            Button::Keyboard(KeyCode::KeyX),
+        ]);
+        let mut frames = Frames::new();
+        assert!(!frames.tap(100, KeyCode::KeyG, &input));
+        assert!(frames.tap(500, KeyCode::KeyX, &input));
+
+        let mut frames = Frames::new();
+        assert!(!frames.tap(100, KeyCode::KeyG, &input));
+        assert!(!frames.tap(1500, KeyCode::KeyX, &input));
+    }
+
+    #[test]
+    fn holds_trigger_once() {
+        let input = UserInput::Hold(Button::Keyboard(KeyCode::KeyF), Duration::from_millis(500));
+        let mut frames = Frames::new();
+        frames.keyboard_input.press(KeyCode::KeyF);
+        let triggered: Vec<_> = (0..10).map(|_| frames.frame(100, &input)).collect();
+        assert_eq!(triggered.iter().filter(|&&triggered| triggered).count(), 1);
+        assert!(triggered[5], "triggers 500ms after the press");
+
+        frames.keyboard_input.release(KeyCode::KeyF);
+        assert!(!frames.frame(100, &input));
+    }
+
+    #[test]
+    fn holds_and_sequences_parse() {
+        let f = Button::Keyboard(KeyCode::KeyF);
+        let hold = UserInput::Hold(Button::Keyboard(KeyCode::KeyX), Duration::from_millis(500));
+        assert_eq!("hold X for 500ms".parse(), Ok(hold.clone()));
+        assert_eq!("Hold x FOR 0.5s".parse(), Ok(hold));
+        assert_eq!("double tap F".parse(), Ok(UserInput::DoubleTap(f.clone())));
+        assert_eq!("Double Tap F".parse(), Ok(UserInput::DoubleTap(f)));
+
+        let sequence = UserInput::Sequence(vec![
+            Button::Keyboard(KeyCode::KeyG),
+            Button::Keyboard(KeyCode::KeyX),
+        ]);
+        assert_eq!("G then X".parse(), Ok(sequence.clone()));
+        assert_eq!("g THEN x".parse(), Ok(sequence.clone()));
+        assert_eq!(sequence.to_string().parse(), Ok(sequence));
+        assert!("hold X".parse::<UserInput>().is_err());
+        assert!("hold X for 5 minutes".parse::<UserInput>().is_err());
+    }
+
+    #[test]
+    fn bindings_dont_trigger_with_extra_modifiers() {
+        let save = Binding::new(UserInput::Single(Button::Keyboard(KeyCode::KeyS)));
+        let mut frames = Frames::new();
+        frames.keyboard_input.press(KeyCode::KeyS);
+        assert!(frames.binding_frame(100, &save));
+        frames.keyboard_input.release(KeyCode::KeyS);
+
+        frames.keyboard_input.press(KeyCode::ControlLeft);
+        frames.keyboard_input.press(KeyCode::KeyS);
+        assert!(!frames.binding_frame(100, &save));
+        frames.keyboard_input.release(KeyCode::KeyS);
+
+        frames.keyboard_input.press(KeyCode::KeyS);
+        assert!(frames.binding_frame(100, &save.allow_extra_modifiers()));
+    }
+
+    fn chord(keys: &[KeyCode]) -> Binding {
+        Binding::new(UserInput::Chord(
+            keys.iter().copied().map(Button::Keyboard).collect(),
+        ))
+    }
+
+    #[test]
+    fn chords_with_different_modifiers_dont_conflict() {
+        let undo = chord(&[KeyCode::ControlLeft, KeyCode::KeyZ]);
+        let redo = chord(&[KeyCode::ControlLeft, KeyCode::ShiftLeft, KeyCode::KeyZ]);
+        assert!(!undo.overlaps(&redo));
+        assert!(undo.allow_extra_modifiers().overlaps(&redo));
+    }
+
+    #[test]
+    fn modifier_chords_only_overlap_with_extra_modifiers_allowed() {
+        let shift = chord(&[KeyCode::ShiftLeft]);
+        let ctrl_shift = chord(&[KeyCode::ControlLeft, KeyCode::ShiftLeft]);
+        assert!(!shift.overlaps(&ctrl_shift));
+        assert!(shift.allow_extra_modifiers().overlaps(&ctrl_shift));
+    }
+
+    #[test]
+    fn excluding_conditions_suppress_conflicts() {
+        let input = UserInput::Single(Button::Keyboard(KeyCode::KeyE));
+        let outside_text = Binding::new(input.clone())
+            .with_conditions([BindingCondition::ListeningForText(false)]);
+        let in_text =
+            Binding::new(input.clone()).with_conditions([BindingCondition::ListeningForText(true)]);
+        assert!(!outside_text.overlaps(&in_text));
+        assert!(outside_text.overlaps(&Binding::new(input)));
+    }
+
+    #[test]
+    fn single_presses_conflict_with_double_taps() {
+        let mut controls = EditorControls::default();
+        controls.insert(
+            Action::FocusSelected,
+            UserInput::Single(Button::Keyboard(KeyCode::KeyF)).into(),
+        );
+        controls.insert(
+            Action::PlayPauseEditor,
+            UserInput::DoubleTap(Button::Keyboard(KeyCode::KeyF)).into(),
+        );
+        assert_eq!(
+            controls.conflicts(),
+            [BindingConflict {
+                first: (Action::PlayPauseEditor, 0),
+                second: (Action::FocusSelected, 0),
+            }]
+        );
+    }
 }
@@ -4,14 +4,26 @@
 #[cfg(feature = "default_windows")]
 pub mod controls;
 
+#[cfg(feature = "default_windows")]
+use std::any::TypeId;
+
+#[cfg(feature = "default_windows")]
+use bevy::utils::HashSet;
 use bevy::{
     prelude::{Entity, Plugin, Update},
     window::{MonitorSelection, Window, WindowPosition, WindowRef, WindowResolution},
 };
+#[cfg(feature = "default_windows")]
+use bevy_editor_pls_core::editor_window::EditorWindow;
+use layout::{DockNode, Layout};
 
 pub use bevy_editor_pls_core::egui_dock;
 #[doc(inline)]
-pub use bevy_editor_pls_core::{editor, editor_window, AddEditorWindow};
+pub use bevy_editor_pls_core::{
+    editor, editor_window, layout, menu, notifications, palette, run_conditions, settings,
+    status_bar, theme, AddEditorWindow, AddMenuItem, AddPaletteCommand, AddStatusBarItem,
+    EditorSet, EditorSettings, EditorTheme, Notification, Notifications,
+};
 pub use egui;
 
 #[cfg(feature = "default_windows")]
@@ -20,13 +32,19 @@ pub use bevy_editor_pls_default_windows as default_windows;
 
 /// Commonly used types and extension traits
 pub mod prelude {
-    pub use crate::{AddEditorWindow, EditorPlugin};
+    #[cfg(feature = "default_windows")]
+    pub use crate::controls::{AddEditorAction, EditorAction};
+    pub use crate::{
+        AddEditorWindow, AddMenuItem, AddPaletteCommand, AddStatusBarItem, EditorPlugin,
+        Notification, Notifications,
+    };
     #[cfg(feature = "default_windows")]
     pub use bevy_editor_pls_default_windows::scenes::NotInScene;
 }
 
 /// Where to show the editor
 #[derive(Default)]
+#[allow(clippy::large_enum_variant)]
 pub enum EditorWindowPlacement {
     /// On the primary window
     #[default]
@@ -50,9 +68,57 @@ pub enum EditorWindowPlacement {
 ///         .run();
 /// }
 /// ```
-#[derive(Default)]
+///
+/// The builder methods pick which of the default windows and plugins get added, and replace the default layout:
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_editor_pls::default_windows::{assets::AssetsWindow, inspector::InspectorWindow};
+/// use bevy_editor_pls::{layout::DockNode, EditorPlugin};
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugins(
+///             EditorPlugin::new()
+///                 .without_default_window::<AssetsWindow>()
+///                 .with_layout(
+///                     DockNode::Viewport.split_right(0.75, DockNode::window::<InspectorWindow>()),
+///                 )
+///                 .without_wireframe_plugin(),
+///         )
+///         .run();
+/// }
+/// ```
 pub struct EditorPlugin {
     pub window: EditorWindowPlacement,
+    layout: Option<DockNode>,
+    /// Whether default windows are added, unless listed in `default_window_exceptions`
+    #[cfg(feature = "default_windows")]
+    all_default_windows: bool,
+    #[cfg(feature = "default_windows")]
+    default_window_exceptions: HashSet<TypeId>,
+    #[cfg(feature = "default_windows")]
+    add_wireframe_plugin: bool,
+    #[cfg(feature = "default_windows")]
+    add_transform_gizmo_plugin: bool,
+}
+
+impl Default for EditorPlugin {
+    fn default() -> Self {
+        EditorPlugin {
+            window: EditorWindowPlacement::default(),
+            layout: None,
+            #[cfg(feature = "default_windows")]
+            all_default_windows: true,
+            #[cfg(feature = "default_windows")]
+            default_window_exceptions: HashSet::default(),
+            #[cfg(feature = "default_windows")]
+            add_wireframe_plugin: true,
+            #[cfg(feature = "default_windows")]
+            add_transform_gizmo_plugin: true,
+        }
+    }
 }
 
 impl EditorPlugin {
@@ -75,6 +141,101 @@ impl EditorPlugin {
             ..Default::default()
         })
     }
+
+    /// Replace the default layout of docked windows.
+    ///
+    /// Windows of the description which don't get added are left out.
+    pub fn with_layout(mut self, dock: DockNode) -> Self {
+        self.layout = Some(dock);
+        self
+    }
+}
+
+#[cfg(feature = "default_windows")]
+impl EditorPlugin {
+    /// Don't add any of the default windows, except for the ones enabled with [`EditorPlugin::with_default_window`]
+    pub fn without_default_windows(mut self) -> Self {
+        self.all_default_windows = false;
+        self.default_window_exceptions.clear();
+        self
+    }
+
+    /// Add the default window `W`, for example after [`EditorPlugin::without_default_windows`]
+    pub fn with_default_window<W: EditorWindow>(mut self) -> Self {
+        let window = TypeId::of::<W>();
+        if self.all_default_windows {
+            self.default_window_exceptions.remove(&window);
+        } else {
+            self.default_window_exceptions.insert(window);
+        }
+        self
+    }
+
+    /// Don't add the default window `W`
+    pub fn without_default_window<W: EditorWindow>(mut self) -> Self {
+        let window = TypeId::of::<W>();
+        if self.all_default_windows {
+            self.default_window_exceptions.insert(window);
+        } else {
+            self.default_window_exceptions.remove(&window);
+        }
+        self
+    }
+
+    /// Don't add the [`WireframePlugin`](bevy::pbr::wireframe::WireframePlugin), for apps which add it themselves
+    pub fn without_wireframe_plugin(mut self) -> Self {
+        self.add_wireframe_plugin = false;
+        self
+    }
+
+    /// Don't add the [`TransformGizmoPlugin`](transform_gizmo_bevy::TransformGizmoPlugin), for apps which add it themselves.
+    ///
+    /// The `Gizmos` window requires it, so it has to be added before the editor runs.
+    pub fn without_transform_gizmo_plugin(mut self) -> Self {
+        self.add_transform_gizmo_plugin = false;
+        self
+    }
+
+    fn is_default_window_enabled(&self, window: TypeId) -> bool {
+        self.all_default_windows != self.default_window_exceptions.contains(&window)
+    }
+
+    /// Adds the default window `W` if it is enabled, otherwise records it in `disabled_windows`
+    fn add_default_window<W: EditorWindow>(
+        &self,
+        app: &mut bevy::prelude::App,
+        disabled_windows: &mut HashSet<TypeId>,
+    ) {
+        let window = TypeId::of::<W>();
+        if self.is_default_window_enabled(window) {
+            app.add_editor_window::<W>();
+        } else {
+            disabled_windows.insert(window);
+        }
+    }
+
+    /// The default window arrangement of the "Scene editing" layout preset
+    pub fn default_layout() -> DockNode {
+        use bevy_editor_pls_default_windows::assets::AssetsWindow;
+        use bevy_editor_pls_default_windows::debug_settings::DebugSettingsWindow;
+        use bevy_editor_pls_default_windows::diagnostics::DiagnosticsWindow;
+        use bevy_editor_pls_default_windows::hierarchy::HierarchyWindow;
+        use bevy_editor_pls_default_windows::inspector::InspectorWindow;
+        use bevy_editor_pls_default_windows::resources::ResourcesWindow;
+
+        DockNode::Viewport
+            .split_below(
+                0.8,
+                DockNode::tabs(&[
+                    TypeId::of::<ResourcesWindow>(),
+                    TypeId::of::<AssetsWindow>(),
+                    TypeId::of::<DebugSettingsWindow>(),
+                    TypeId::of::<DiagnosticsWindow>(),
+                ]),
+            )
+            .split_left(0.2, DockNode::window::<HierarchyWindow>())
+            .split_right(0.75, DockNode::window::<InspectorWindow>())
+    }
 }
 
 impl Plugin for EditorPlugin {
@@ -99,8 +260,17 @@ impl Plugin for EditorPlugin {
         //     app.add_plugins(bevy_framepace::debug::DiagnosticsPlugin);
         // }
 
+        #[cfg(not(feature = "default_windows"))]
+        if let Some(dock) = &self.layout {
+            let mut internal_state = app
+                .world_mut()
+                .resource_mut::<editor::EditorInternalState>();
+            *internal_state.layout_mut() = Layout::from_dock(dock);
+        }
+
         #[cfg(feature = "default_windows")]
         {
+            use bevy::prelude::{resource_changed, IntoSystemConfigs};
             use bevy_editor_pls_default_windows::add::AddWindow;
             use bevy_editor_pls_default_windows::assets::AssetsWindow;
             use bevy_editor_pls_default_windows::cameras::CameraWindow;
@@ -109,49 +279,109 @@ impl Plugin for EditorPlugin {
             use bevy_editor_pls_default_windows::gizmos::GizmoWindow;
             use bevy_editor_pls_default_windows::hierarchy::HierarchyWindow;
             use bevy_editor_pls_default_windows::inspector::InspectorWindow;
+            use bevy_editor_pls_default_windows::play_mode::PlayModePlugin;
+            use bevy_editor_pls_default_windows::preferences::PreferencesWindow;
             use bevy_editor_pls_default_windows::renderer::RendererWindow;
             use bevy_editor_pls_default_windows::resources::ResourcesWindow;
             use bevy_editor_pls_default_windows::scenes::SceneWindow;
+            use bevy_editor_pls_default_windows::time_control::TimeControlWindow;
+
+            // windows of other plugins aren't known yet, the core plugin drops the ones never added in `finish`
+            let mut disabled_windows = HashSet::default();
+            self.add_default_window::<HierarchyWindow>(app, &mut disabled_windows);
+            self.add_default_window::<AssetsWindow>(app, &mut disabled_windows);
+            self.add_default_window::<InspectorWindow>(app, &mut disabled_windows);
+            self.add_default_window::<DebugSettingsWindow>(app, &mut disabled_windows);
+            self.add_default_window::<AddWindow>(app, &mut disabled_windows);
+            self.add_default_window::<DiagnosticsWindow>(app, &mut disabled_windows);
+            self.add_default_window::<RendererWindow>(app, &mut disabled_windows);
+            self.add_default_window::<CameraWindow>(app, &mut disabled_windows);
+            self.add_default_window::<ResourcesWindow>(app, &mut disabled_windows);
+            self.add_default_window::<SceneWindow>(app, &mut disabled_windows);
+            self.add_default_window::<GizmoWindow>(app, &mut disabled_windows);
+            self.add_default_window::<controls::ControlsWindow>(app, &mut disabled_windows);
+            self.add_default_window::<PreferencesWindow>(app, &mut disabled_windows);
+            self.add_default_window::<TimeControlWindow>(app, &mut disabled_windows);
 
-            app.add_editor_window::<HierarchyWindow>();
-            app.add_editor_window::<AssetsWindow>();
-            app.add_editor_window::<InspectorWindow>();
-            app.add_editor_window::<DebugSettingsWindow>();
-            app.add_editor_window::<AddWindow>();
-            app.add_editor_window::<DiagnosticsWindow>();
-            app.add_editor_window::<RendererWindow>();
-            app.add_editor_window::<CameraWindow>();
-            app.add_editor_window::<ResourcesWindow>();
-            app.add_editor_window::<SceneWindow>();
-            app.add_editor_window::<GizmoWindow>();
-            app.add_editor_window::<controls::ControlsWindow>();
-
-            app.add_plugins(bevy::pbr::wireframe::WireframePlugin);
+            if !app.is_plugin_added::<PlayModePlugin>() {
+                app.add_plugins(PlayModePlugin);
+            }
+
+            if self.add_wireframe_plugin
+                && !app.is_plugin_added::<bevy::pbr::wireframe::WireframePlugin>()
+            {
+                app.add_plugins(bevy::pbr::wireframe::WireframePlugin);
+            }
 
             // required for the GizmoWindow
-            if !app.is_plugin_added::<transform_gizmo_bevy::TransformGizmoPlugin>() {
+            if self.add_transform_gizmo_plugin
+                && !app.is_plugin_added::<transform_gizmo_bevy::TransformGizmoPlugin>()
+            {
                 app.add_plugins(transform_gizmo_bevy::TransformGizmoPlugin);
             }
 
             app.insert_resource(controls::EditorControls::default_bindings())
-                .add_systems(Update, controls::editor_controls_system);
+                .init_resource::<controls::EditorActions>()
+                .add_systems(
+                    Update,
+                    (
+                        controls::bind_editor_actions
+                            .run_if(resource_changed::<controls::EditorActions>),
+                        controls::hot_reload_keymap,
+                        controls::editor_controls_system,
+                    )
+                        .chain(),
+                );
 
-            let mut internal_state = app.world_mut().resource_mut::<editor::EditorInternalState>();
+            for action in controls::Action::all() {
+                if action == controls::Action::OpenCommandPalette {
+                    continue;
+                }
+                app.add_palette_command(
+                    action.to_string(),
+                    move |world: &mut bevy::prelude::World| controls::run_action(world, action),
+                );
+            }
 
-            let [game, _inspector] =
-                internal_state.split_right::<InspectorWindow>(egui_dock::NodeIndex::root(), 0.75);
-            let [game, _hierarchy] = internal_state.split_left::<HierarchyWindow>(game, 0.2);
-            let [_game, _bottom] = internal_state.split_many(
-                game,
-                0.8,
-                egui_dock::Split::Below,
-                &[
-                    std::any::TypeId::of::<ResourcesWindow>(),
-                    std::any::TypeId::of::<AssetsWindow>(),
-                    std::any::TypeId::of::<DebugSettingsWindow>(),
-                    std::any::TypeId::of::<DiagnosticsWindow>(),
-                ],
-            );
+            let layout = |dock: DockNode| {
+                dock.retain_windows(&|window| !disabled_windows.contains(&window))
+                    .map_or_else(Layout::new, |dock| Layout::from_dock(&dock))
+            };
+
+            let mut internal_state = app
+                .world_mut()
+                .resource_mut::<editor::EditorInternalState>();
+
+            let scene_editing = self
+                .layout
+                .clone()
+                .unwrap_or_else(EditorPlugin::default_layout);
+            *internal_state.layout_mut() = layout(scene_editing);
+            internal_state.save_layout_preset("Scene editing");
+
+            let debugging = DockNode::Viewport
+                .split_below(
+                    0.6,
+                    DockNode::tabs(&[
+                        TypeId::of::<DebugSettingsWindow>(),
+                        TypeId::of::<ResourcesWindow>(),
+                        TypeId::of::<CameraWindow>(),
+                    ]),
+                )
+                .split_left(0.2, DockNode::window::<HierarchyWindow>())
+                .split_right(0.75, DockNode::window::<InspectorWindow>());
+            internal_state.add_layout_preset("Debugging", layout(debugging));
+
+            let profiling = DockNode::Viewport
+                .split_below(0.75, DockNode::window::<DebugSettingsWindow>())
+                .split_right(
+                    0.7,
+                    DockNode::tabs(&[
+                        TypeId::of::<DiagnosticsWindow>(),
+                        TypeId::of::<RendererWindow>(),
+                    ]),
+                );
+            internal_state.add_layout_preset("Profiling", layout(profiling));
         }
     }
 }
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use bevy::render::settings::WgpuSettings;
+use bevy::render::RenderPlugin;
+use bevy::window::ExitCondition;
+use bevy::winit::WinitPlugin;
+use bevy_editor_pls::default_windows::assets::AssetsWindow;
+use bevy_editor_pls::default_windows::hierarchy::HierarchyWindow;
+use bevy_editor_pls::default_windows::inspector::InspectorWindow;
+use bevy_editor_pls::default_windows::resources::ResourcesWindow;
+use bevy_editor_pls::editor::Editor;
+use bevy_editor_pls::editor_window::{EditorWindow, EditorWindowContext};
+use bevy_editor_pls::layout::{DockNode, EditorPersistence};
+use bevy_editor_pls::EditorPlugin;
+
+struct UnregisteredWindow;
+
+impl EditorWindow for UnregisteredWindow {
+    type State = ();
+    const NAME: &'static str = "Unregistered";
+
+    fn ui(_world: &mut World, _cx: EditorWindowContext, _ui: &mut egui::Ui) {}
+}
+
+/// Shows the windows which read each other's state at the same time
+fn dependent_windows() -> DockNode {
+    DockNode::Viewport
+        .split_below(
+            0.7,
+            DockNode::window::<ResourcesWindow>()
+                .split_right(0.5, DockNode::window::<AssetsWindow>()),
+        )
+        .split_left(0.2, DockNode::window::<HierarchyWindow>())
+        .split_right(0.75, DockNode::window::<InspectorWindow>())
+}
+
+/// Runs a few frames of an app without a renderer or an event loop, with the editor shown
+fn run_editor(plugin: EditorPlugin) -> App {
+    let mut app = App::new();
+    app.add_plugins(
+        DefaultPlugins
+            .set(RenderPlugin {
+                render_creation: WgpuSettings {
+                    backends: None,
+                    ..default()
+                }
+                .into(),
+                ..default()
+            })
+            .set(WindowPlugin {
+                exit_condition: ExitCondition::DontExit,
+                ..default()
+            })
+            .disable::<WinitPlugin>(),
+    )
+    .insert_resource(EditorPersistence {
+        dir: None,
+        user_dir: None,
+    })
+    .add_plugins(plugin);
+    app.finish();
+    app.cleanup();
+    app.world_mut().resource_mut::<Editor>().set_active(true);
+    for _ in 0..3 {
+        app.update();
+    }
+    app
+}
+
+#[test]
+fn windows_work_without_the_hierarchy() {
+    let app = run_editor(
+        EditorPlugin::new()
+            .without_default_window::<HierarchyWindow>()
+            .with_layout(dependent_windows()),
+    );
+    let editor = app.world().resource::<Editor>();
+    assert!(editor.window_state::<HierarchyWindow>().is_none());
+    assert!(editor.window_state::<InspectorWindow>().is_some());
+}
+
+#[test]
+fn windows_work_without_the_inspector() {
+    let app = run_editor(
+        EditorPlugin::new()
+            .without_default_window::<InspectorWindow>()
+            .with_layout(dependent_windows()),
+    );
+    let editor = app.world().resource::<Editor>();
+    assert!(editor.window_state::<InspectorWindow>().is_none());
+}
+
+#[test]
+fn unregistered_windows_are_left_out_of_the_layout() {
+    let dock = DockNode::Viewport.split_right(0.75, DockNode::window::<UnregisteredWindow>());
+    run_editor(EditorPlugin::new().with_layout(dock));
+}
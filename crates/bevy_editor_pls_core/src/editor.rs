@@ -1,18 +1,80 @@
 use std::any::{Any, TypeId};
-
-use bevy::window::WindowMode;
-use bevy::{prelude::*, utils::HashMap};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use bevy::window::{WindowMode, WindowResolution};
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
 use bevy_inspector_egui::bevy_egui::{egui, EguiContext};
-use egui_dock::{NodeIndex, SurfaceIndex, TabBarStyle, TabIndex};
+use egui_dock::{NodeIndex, SurfaceIndex, TabBarStyle};
 use indexmap::IndexMap;
 
 use crate::editor_window::{EditorWindow, EditorWindowContext};
-
+use crate::layout::Layout;
+use crate::menu::{self, EditorMenus};
+use crate::notifications::{self, Notifications};
+use crate::palette::{self, CommandPaletteState};
+use crate::status_bar;
+
+/// Events sent by the editor, and requests like [`EditorEvent::SelectEntity`] sent to it.
+///
+/// Windows are identified by the [`TypeId`] of their [`EditorWindow`] type and their instance, where `0` is the primary instance.
 #[non_exhaustive]
-#[derive(Event)]
+#[derive(Event, Clone, Debug)]
 pub enum EditorEvent {
-    Toggle { now_active: bool },
+    Toggle {
+        now_active: bool,
+    },
     FocusSelected,
+    /// Request to replace the selection with the given entity
+    SelectEntity(Entity),
+    /// The entities selected in the hierarchy changed
+    SelectionChanged {
+        selected: Vec<Entity>,
+    },
+    /// A window was docked or opened as a floating window
+    WindowOpened {
+        window: TypeId,
+        name: &'static str,
+        instance: u32,
+    },
+    WindowClosed {
+        window: TypeId,
+        name: &'static str,
+        instance: u32,
+    },
+    /// A docked window became the focused tab
+    WindowFocused {
+        window: TypeId,
+        name: &'static str,
+        instance: u32,
+    },
+    /// Tabs were opened, closed or moved, for example by switching to the layout preset `preset`
+    LayoutChanged {
+        preset: Option<String>,
+    },
+    /// An entity was renamed in the editor
+    EntityRenamed {
+        entity: Entity,
+        name: Name,
+    },
+    /// An entity was despawned in the editor, together with its descendants if `recursive`
+    EntityDespawned {
+        entity: Entity,
+        recursive: bool,
+    },
+    /// The scene was saved to `path` from the editor
+    SceneSaved {
+        path: PathBuf,
+    },
+    /// The editor camera of `viewport` was switched to the camera called `camera`
+    CameraSwitched {
+        viewport: u32,
+        camera: &'static str,
+    },
 }
 
 #[derive(Debug)]
@@ -31,10 +93,16 @@ pub struct Editor {
     pointer_used: bool,
     active_editor_interaction: Option<ActiveEditorInteraction>,
     listening_for_text: bool,
-    viewport: egui::Rect,
+    /// Rects of the viewports drawn in the last frame, keyed by viewport id
+    viewports: BTreeMap<u32, egui::Rect>,
+    active_viewport: u32,
+    viewport_textures: HashMap<u32, egui::TextureId>,
 
     windows: IndexMap<TypeId, EditorWindowData>,
     window_states: HashMap<TypeId, EditorWindowState>,
+    /// States of additional window instances, keyed by window type and instance number.
+    /// The primary instance `0` lives in `window_states`.
+    instance_states: HashMap<(TypeId, u32), EditorWindowState>,
 }
 impl Editor {
     pub fn new(on_window: Entity, always_active: bool) -> Self {
@@ -46,10 +114,13 @@ impl Editor {
             pointer_used: false,
             active_editor_interaction: None,
             listening_for_text: false,
-            viewport: egui::Rect::from_min_size(egui::Pos2::ZERO, egui::Vec2::new(640., 480.)),
+            viewports: BTreeMap::new(),
+            active_viewport: 0,
+            viewport_textures: HashMap::default(),
 
             windows: IndexMap::default(),
             window_states: HashMap::default(),
+            instance_states: HashMap::default(),
         }
     }
 
@@ -72,11 +143,45 @@ impl Editor {
         self.active = active;
     }
 
+    /// Rect of the [active viewport](Editor::active_viewport)
     pub fn viewport(&self) -> egui::Rect {
-        self.viewport
+        self.viewport_rect(self.active_viewport).unwrap_or_else(|| {
+            egui::Rect::from_min_size(egui::Pos2::ZERO, egui::Vec2::new(640., 480.))
+        })
+    }
+    /// Rect of the viewport `viewport`, if it was drawn in the last frame
+    pub fn viewport_rect(&self, viewport: u32) -> Option<egui::Rect> {
+        self.viewports.get(&viewport).copied()
+    }
+    /// Ids and rects of all viewports drawn in the last frame. The primary viewport has the id `0`.
+    pub fn viewports(&self) -> impl Iterator<Item = (u32, egui::Rect)> + '_ {
+        self.viewports.iter().map(|(&id, &rect)| (id, rect))
+    }
+    /// The viewport the pointer is over, or was over last. Editor camera input goes to this viewport.
+    pub fn active_viewport(&self) -> u32 {
+        self.active_viewport
+    }
+    pub fn viewport_at(&self, pos: egui::Pos2) -> Option<u32> {
+        self.viewports
+            .iter()
+            .find(|(_, rect)| rect.contains(pos))
+            .map(|(&id, _)| id)
     }
     pub fn is_in_viewport(&self, pos: egui::Pos2) -> bool {
-        self.viewport.contains(pos)
+        self.viewport_at(pos).is_some()
+    }
+
+    /// Fill the viewport `viewport` with `texture`, e.g. the offscreen render target of its editor camera.
+    ///
+    /// Without a texture, the viewport is transparent and shows what is rendered to the window behind it.
+    pub fn set_viewport_texture(&mut self, viewport: u32, texture: Option<egui::TextureId>) {
+        match texture {
+            Some(texture) => self.viewport_textures.insert(viewport, texture),
+            None => self.viewport_textures.remove(&viewport),
+        };
+    }
+    pub fn viewport_texture(&self, viewport: u32) -> Option<egui::TextureId> {
+        self.viewport_textures.get(&viewport).copied()
     }
 
     pub fn pointer_used(&self) -> bool {
@@ -103,48 +208,108 @@ impl Editor {
 pub(crate) type UiFn =
     Box<dyn Fn(&mut World, EditorWindowContext, &mut egui::Ui) + Send + Sync + 'static>;
 pub(crate) type EditorWindowState = Box<dyn Any + Send + Sync>;
+/// Deferred action of a menu item or palette entry
+pub(crate) type CommandFn = Arc<dyn Fn(&mut World) + Send + Sync>;
 
 struct EditorWindowData {
     name: &'static str,
+    /// Stable identifier used when persisting the layout
+    id: &'static str,
     ui_fn: UiFn,
     menu_ui_fn: UiFn,
     viewport_toolbar_ui_fn: UiFn,
     viewport_ui_fn: UiFn,
+    default_state_fn: fn() -> EditorWindowState,
+    save_state_fn: fn(&EditorWindowState) -> Option<String>,
+    load_state_fn: fn(&mut EditorWindowState, &str),
     default_size: (f32, f32),
+    multiple_instances: bool,
 }
 
-#[derive(Resource)]
+#[derive(Resource, Default)]
 pub struct EditorInternalState {
-    state: egui_dock::DockState<TreeTab>,
-    pub(crate) floating_windows: Vec<FloatingWindow>,
+    pub(crate) layout: Layout,
+    pub(crate) layout_presets: IndexMap<String, Layout>,
+    pub(crate) active_layout_preset: Option<String>,
+    pub(crate) new_layout_preset_name: String,
+    pub(crate) palette: CommandPaletteState,
+    /// Commands picked in the menu bar or palette, run once the editor ui is done with the world
+    pub(crate) pending_commands: Vec<CommandFn>,
+
+    /// Tabs shown in their own OS window instead of the dock
+    pub(crate) popped_out_windows: Vec<PoppedOutWindow>,
 
     next_floating_window_id: u32,
+    next_window_instance: u32,
+    /// The layout when the window and layout events were last sent
+    layout_snapshot: Option<LayoutSnapshot>,
 }
 
-impl Default for EditorInternalState {
-    fn default() -> Self {
-        Self {
-            state: egui_dock::DockState::new(vec![TreeTab::GameView]),
-            floating_windows: Default::default(),
-            next_floating_window_id: Default::default(),
+#[derive(PartialEq)]
+struct LayoutSnapshot {
+    tabs: Vec<((SurfaceIndex, NodeIndex), TreeTab)>,
+    floating_windows: Vec<(TypeId, u32)>,
+    popped_out_windows: Vec<(TypeId, u32)>,
+    focused: Option<TreeTab>,
+}
+
+impl LayoutSnapshot {
+    fn take(layout: &mut Layout, popped_out_windows: &[PoppedOutWindow]) -> Self {
+        let tabs = layout
+            .state
+            .iter_all_tabs()
+            .map(|(location, &tab)| (location, tab))
+            .collect();
+        let mut floating_windows: Vec<_> = layout
+            .floating_windows
+            .iter()
+            .map(|floating| (floating.window, floating.instance))
+            .collect();
+        floating_windows.sort_unstable_by_key(|&(window, instance)| (instance, window));
+        let popped_out_windows = popped_out_windows
+            .iter()
+            .map(|popped_out| (popped_out.window, popped_out.instance))
+            .collect();
+        let focused = layout.state.find_active_focused().map(|(_, tab)| *tab);
+        LayoutSnapshot {
+            tabs,
+            floating_windows,
+            popped_out_windows,
+            focused,
         }
     }
+
+    fn windows(&self) -> HashSet<(TypeId, u32)> {
+        let docked = self.tabs.iter().filter_map(|(_, tab)| match *tab {
+            TreeTab::CustomWindow(window, instance) => Some((window, instance)),
+            TreeTab::GameView(_) => None,
+        });
+        docked
+            .chain(self.floating_windows.iter().copied())
+            .chain(self.popped_out_windows.iter().copied())
+            .collect()
+    }
 }
 
-#[derive(Copy, Clone)]
-enum TreeTab {
-    GameView,
-    CustomWindow(TypeId),
+#[derive(Copy, Clone, PartialEq)]
+pub(crate) enum TreeTab {
+    /// A game viewport, where `0` is the primary viewport
+    GameView(u32),
+    /// A window type and its instance, where `0` is the primary instance
+    CustomWindow(TypeId, u32),
 }
 
 impl EditorInternalState {
+    /// The currently shown layout
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+    pub fn layout_mut(&mut self) -> &mut Layout {
+        &mut self.layout
+    }
+
     pub fn push_to_focused_leaf<W: EditorWindow>(&mut self) {
-        self.state
-            .push_to_focused_leaf(TreeTab::CustomWindow(TypeId::of::<W>()));
-        if let Some((surface_index, node_index)) = self.state.focused_leaf() {
-            self.state
-                .set_active_tab((surface_index, node_index, TabIndex(0)));
-        };
+        self.layout.push_to_focused_leaf::<W>();
     }
 
     pub fn split<W: EditorWindow>(
@@ -153,9 +318,7 @@ impl EditorInternalState {
         split: egui_dock::Split,
         fraction: f32,
     ) -> [NodeIndex; 2] {
-        let node = egui_dock::Node::leaf(TreeTab::CustomWindow(TypeId::of::<W>()));
-        self.state
-            .split((SurfaceIndex::main(), parent), split, fraction, node)
+        self.layout.split::<W>(parent, split, fraction)
     }
 
     pub fn split_right<W: EditorWindow>(
@@ -163,28 +326,28 @@ impl EditorInternalState {
         parent: NodeIndex,
         fraction: f32,
     ) -> [NodeIndex; 2] {
-        self.split::<W>(parent, egui_dock::Split::Right, fraction)
+        self.layout.split_right::<W>(parent, fraction)
     }
     pub fn split_left<W: EditorWindow>(
         &mut self,
         parent: NodeIndex,
         fraction: f32,
     ) -> [NodeIndex; 2] {
-        self.split::<W>(parent, egui_dock::Split::Left, fraction)
+        self.layout.split_left::<W>(parent, fraction)
     }
     pub fn split_above<W: EditorWindow>(
         &mut self,
         parent: NodeIndex,
         fraction: f32,
     ) -> [NodeIndex; 2] {
-        self.split::<W>(parent, egui_dock::Split::Above, fraction)
+        self.layout.split_above::<W>(parent, fraction)
     }
     pub fn split_below<W: EditorWindow>(
         &mut self,
         parent: NodeIndex,
         fraction: f32,
     ) -> [NodeIndex; 2] {
-        self.split::<W>(parent, egui_dock::Split::Below, fraction)
+        self.layout.split_below::<W>(parent, fraction)
     }
 
     pub fn split_many(
@@ -194,26 +357,160 @@ impl EditorInternalState {
         split: egui_dock::Split,
         windows: &[TypeId],
     ) -> [NodeIndex; 2] {
-        let tabs = windows.iter().copied().map(TreeTab::CustomWindow).collect();
-        let node = egui_dock::Node::leaf_with(tabs);
-        self.state
-            .split((SurfaceIndex::main(), parent), split, fraction, node)
+        self.layout.split_many(parent, fraction, split, windows)
     }
 }
 
 #[derive(Clone)]
 pub(crate) struct FloatingWindow {
     pub(crate) window: TypeId,
+    pub(crate) instance: u32,
     pub(crate) id: u32,
     pub(crate) initial_position: Option<egui::Pos2>,
+    /// Last known rect of the window, used to restore it at the same place
+    pub(crate) rect: Option<egui::Rect>,
+}
+
+/// A tab which was popped out of the dock into its own OS window
+pub(crate) struct PoppedOutWindow {
+    pub(crate) window: TypeId,
+    pub(crate) instance: u32,
+    /// The [`Window`] entity, spawned at the start of the next editor frame
+    pub(crate) entity: Option<Entity>,
 }
 
+/// Marks the OS windows of tabs popped out of the dock.
+///
+/// Closing such a window docks its tab back into the editor.
+#[derive(Component)]
+pub struct PopOutWindow;
+
 impl EditorInternalState {
     pub(crate) fn next_floating_window_id(&mut self) -> u32 {
         let id = self.next_floating_window_id;
         self.next_floating_window_id += 1;
         id
     }
+
+    /// Returns a window instance number or viewport id which is not used in any layout yet
+    pub(crate) fn next_window_instance(&mut self) -> u32 {
+        self.next_window_instance += 1;
+        self.next_window_instance
+    }
+
+    /// Make sure instances handed out in the future don't clash with the ones in the loaded layouts
+    pub(crate) fn reserve_window_instances(&mut self) {
+        let max_instance = std::iter::once(&self.layout)
+            .chain(self.layout_presets.values())
+            .map(Layout::max_window_instance)
+            .max()
+            .unwrap_or(0);
+        self.next_window_instance = self.next_window_instance.max(max_instance);
+    }
+
+    /// Open a new instance of `window` as a floating window and return its instance number
+    pub(crate) fn open_window_instance(&mut self, window: TypeId) -> u32 {
+        let instance = self.next_window_instance();
+        let id = self.next_floating_window_id();
+        self.layout.floating_windows.push(FloatingWindow {
+            window,
+            instance,
+            id,
+            initial_position: None,
+            rect: None,
+        });
+        instance
+    }
+
+    /// Focuses the tab of `window` if it is docked, and opens it as a floating window otherwise
+    pub(crate) fn focus_or_open_window(&mut self, window: TypeId) {
+        let tab = TreeTab::CustomWindow(window, 0);
+        if let Some((surface, node, tab_index)) = self.layout.state.find_tab(&tab) {
+            self.layout.state.set_active_tab((surface, node, tab_index));
+            self.layout.state.set_focused_node_and_surface((surface, node));
+            return;
+        }
+
+        let is_floating = self
+            .layout
+            .floating_windows
+            .iter()
+            .any(|floating| floating.window == window && floating.instance == 0);
+        if !is_floating {
+            let id = self.next_floating_window_id();
+            self.layout.floating_windows.push(FloatingWindow {
+                window,
+                instance: 0,
+                id,
+                initial_position: None,
+                rect: None,
+            });
+        }
+    }
+
+    fn contains_window_instance(&self, window: TypeId, instance: u32) -> bool {
+        let popped_out = self
+            .popped_out_windows
+            .iter()
+            .any(|popped_out| popped_out.window == window && popped_out.instance == instance);
+        popped_out
+            || std::iter::once(&self.layout)
+                .chain(self.layout_presets.values())
+                .any(|layout| layout.contains_window_instance(window, instance))
+    }
+
+    /// Moves the tab of `W` out of the dock into its own OS window, which is docked back when closed
+    pub fn pop_out_window<W: EditorWindow>(&mut self) {
+        self.pop_out(TypeId::of::<W>(), 0);
+    }
+
+    pub(crate) fn pop_out(&mut self, window: TypeId, instance: u32) {
+        let already_popped_out = self
+            .popped_out_windows
+            .iter()
+            .any(|popped_out| popped_out.window == window && popped_out.instance == instance);
+        if !already_popped_out {
+            self.popped_out_windows.push(PoppedOutWindow {
+                window,
+                instance,
+                entity: None,
+            });
+        }
+    }
+
+    /// Open an additional viewport next to an existing one and return its id
+    pub fn open_viewport(&mut self) -> u32 {
+        let viewport = self.next_window_instance();
+        let tab = TreeTab::GameView(viewport);
+
+        let existing = self
+            .layout
+            .state
+            .iter_all_tabs()
+            .find_map(|((surface, node), tab)| {
+                (surface == SurfaceIndex::main() && matches!(tab, TreeTab::GameView(_)))
+                    .then_some(node)
+            });
+        match existing {
+            Some(node) => {
+                self.layout.state.split(
+                    (SurfaceIndex::main(), node),
+                    egui_dock::Split::Right,
+                    0.5,
+                    egui_dock::Node::leaf(tab),
+                );
+            }
+            None => self.layout.state.push_to_focused_leaf(tab),
+        }
+        viewport
+    }
+
+    /// Whether the viewport `viewport` is open in the current layout or any layout preset
+    pub fn contains_viewport(&self, viewport: u32) -> bool {
+        std::iter::once(&self.layout)
+            .chain(self.layout_presets.values())
+            .any(|layout| layout.contains_viewport(viewport))
+    }
 }
 
 fn ui_fn<W: EditorWindow>(world: &mut World, cx: EditorWindowContext, ui: &mut egui::Ui) {
@@ -232,6 +529,17 @@ fn viewport_toolbar_ui_fn<W: EditorWindow>(
 fn viewport_ui_fn<W: EditorWindow>(world: &mut World, cx: EditorWindowContext, ui: &mut egui::Ui) {
     W::viewport_ui(world, cx, ui);
 }
+fn default_state_fn<W: EditorWindow>() -> EditorWindowState {
+    Box::<W::State>::default()
+}
+fn save_state_fn<W: EditorWindow>(state: &EditorWindowState) -> Option<String> {
+    W::save_state(state.downcast_ref::<W::State>()?)
+}
+fn load_state_fn<W: EditorWindow>(state: &mut EditorWindowState, saved: &str) {
+    if let Some(state) = state.downcast_mut::<W::State>() {
+        W::load_state(state, saved);
+    }
+}
 
 impl Editor {
     pub fn add_window<W: EditorWindow>(&mut self) {
@@ -245,17 +553,68 @@ impl Editor {
             menu_ui_fn,
             viewport_toolbar_ui_fn,
             viewport_ui_fn,
+            default_state_fn: default_state_fn::<W>,
+            save_state_fn: save_state_fn::<W>,
+            load_state_fn: load_state_fn::<W>,
             name: W::NAME,
+            id: W::ID,
             default_size: W::DEFAULT_SIZE,
+            multiple_instances: W::MULTIPLE_INSTANCES,
         };
+        if self.window_by_id(W::ID).is_some() {
+            warn!(
+                "window {} has the same id `{}` as another window, its saved layout and state will be mixed up. Override `EditorWindow::ID` to make it unique",
+                std::any::type_name::<W>(),
+                W::ID
+            );
+        }
         if self.windows.insert(type_id, data).is_some() {
             panic!(
                 "window of type {} already inserted",
                 std::any::type_name::<W>()
             );
         }
-        self.window_states
-            .insert(type_id, Box::<<W as EditorWindow>::State>::default());
+        self.window_states.insert(type_id, default_state_fn::<W>());
+    }
+
+    /// Stable identifier of a registered window, used for persistence
+    pub fn window_id(&self, window: TypeId) -> Option<&'static str> {
+        self.windows.get(&window).map(|data| data.id)
+    }
+    pub(crate) fn window_names(&self) -> impl Iterator<Item = (TypeId, &'static str)> + '_ {
+        self.windows
+            .iter()
+            .map(|(&type_id, data)| (type_id, data.name))
+    }
+    pub fn window_by_id(&self, id: &str) -> Option<TypeId> {
+        self.windows
+            .iter()
+            .find(|(_, data)| data.id == id)
+            .map(|(&type_id, _)| type_id)
+    }
+
+    /// The states saved by windows implementing [`EditorWindow::save_state`], keyed by [`Editor::window_id`]
+    pub fn save_window_states(&self) -> BTreeMap<String, String> {
+        self.windows
+            .iter()
+            .filter_map(|(window, data)| {
+                let saved = (data.save_state_fn)(self.window_states.get(window)?)?;
+                Some((data.id.to_owned(), saved))
+            })
+            .collect()
+    }
+
+    /// Restores states saved by [`Editor::save_window_states`]. Windows which are no longer registered are skipped.
+    pub fn load_window_states(&mut self, saved: &BTreeMap<String, String>) {
+        for (id, saved) in saved {
+            let Some(window) = self.window_by_id(id) else {
+                continue;
+            };
+            let load_state_fn = self.windows[&window].load_state_fn;
+            if let Some(state) = self.window_states.get_mut(&window) {
+                load_state_fn(state, saved);
+            }
+        }
     }
 
     pub fn window_state_mut<W: EditorWindow>(&mut self) -> Option<&mut W::State> {
@@ -268,10 +627,64 @@ impl Editor {
             .get(&TypeId::of::<W>())
             .and_then(|s| s.downcast_ref::<W::State>())
     }
+
+    /// State of a specific instance of `W`. Instance `0` is the primary instance returned by [`Editor::window_state`].
+    pub fn window_instance_state<W: EditorWindow>(&self, instance: u32) -> Option<&W::State> {
+        if instance == 0 {
+            return self.window_state::<W>();
+        }
+        self.instance_states
+            .get(&(TypeId::of::<W>(), instance))
+            .and_then(|s| s.downcast_ref::<W::State>())
+    }
+    pub fn window_instance_state_mut<W: EditorWindow>(
+        &mut self,
+        instance: u32,
+    ) -> Option<&mut W::State> {
+        if instance == 0 {
+            return self.window_state_mut::<W>();
+        }
+        self.instance_states
+            .get_mut(&(TypeId::of::<W>(), instance))
+            .and_then(|s| s.downcast_mut::<W::State>())
+    }
+
+    fn window_title(&self, window: TypeId, instance: u32) -> egui::WidgetText {
+        let name = self.windows[&window].name;
+        match instance {
+            0 => name.into(),
+            instance => format!("{name} ({})", instance + 1).into(),
+        }
+    }
+
+    /// Swaps the state of a secondary window instance with the primary state,
+    /// so that the window's ui can access it through [`EditorWindowContext::state`].
+    fn swap_instance_state(&mut self, window: TypeId, instance: u32) {
+        let default_state_fn = self.windows[&window].default_state_fn;
+        let instance_state = self
+            .instance_states
+            .entry((window, instance))
+            .or_insert_with(default_state_fn);
+        let primary_state = self.window_states.get_mut(&window).unwrap();
+        std::mem::swap(primary_state, instance_state);
+    }
+
+    /// Drops the state of window instances which are neither in the current layout nor in any preset
+    fn drop_closed_window_instances(&mut self, internal_state: &EditorInternalState) {
+        if self.instance_states.is_empty() {
+            return;
+        }
+        self.instance_states.retain(|&(window, instance), _| {
+            internal_state.contains_window_instance(window, instance)
+        });
+    }
 }
 
 impl Editor {
     pub(crate) fn system(world: &mut World) {
+        EditorMenus::update_enabled(world);
+        Editor::update_popped_out_windows(world);
+
         world.resource_scope(|world, mut editor: Mut<Editor>| {
             let Ok(mut egui_context) = world
                 .query::<&mut EguiContext>()
@@ -283,17 +696,26 @@ impl Editor {
 
             world.resource_scope(
                 |world, mut editor_internal_state: Mut<EditorInternalState>| {
-                    world.resource_scope(|world, mut editor_events: Mut<Events<EditorEvent>>| {
-                        editor.editor_ui(
-                            world,
-                            &egui_context,
-                            &mut editor_internal_state,
-                            &mut editor_events,
-                        );
-                    });
+                    // the events of the editor ui are buffered, so that `Events<EditorEvent>` stays in the world
+                    // and windows can send them with `World::send_event` while they are drawn
+                    let mut editor_events = Events::default();
+                    editor.editor_ui(
+                        world,
+                        &egui_context,
+                        &mut editor_internal_state,
+                        &mut editor_events,
+                    );
+                    world.send_event_batch(editor_events.drain());
+                    editor.editor_popped_out_windows(world, &mut editor_internal_state);
                 },
             );
         });
+
+        let pending_commands =
+            std::mem::take(&mut world.resource_mut::<EditorInternalState>().pending_commands);
+        for command in pending_commands {
+            command(world);
+        }
     }
 
     fn editor_ui(
@@ -303,16 +725,24 @@ impl Editor {
         internal_state: &mut EditorInternalState,
         editor_events: &mut Events<EditorEvent>,
     ) {
+        self.drop_closed_window_instances(internal_state);
+        self.send_layout_events(internal_state, editor_events);
+        self.viewports.clear();
         self.editor_menu_bar(world, ctx, internal_state, editor_events);
+        palette::command_palette_ui(world, ctx, self, internal_state, editor_events);
 
         if !self.active {
             self.editor_floating_windows(world, ctx, internal_state);
+            notifications::toasts_ui(ctx, &mut world.resource_mut::<Notifications>());
             self.pointer_used = ctx.wants_pointer_input();
             return;
         }
 
+        // panels have to be added before the dock area, which fills the remaining space
+        status_bar::status_bar_ui(self, world, ctx);
+
         let mut tree = std::mem::replace(
-            &mut internal_state.state,
+            &mut internal_state.layout.state,
             egui_dock::DockState::new(Vec::new()),
         );
 
@@ -332,12 +762,14 @@ impl Editor {
                     world,
                 },
             );
-        internal_state.state = tree;
+        internal_state.layout.state = tree;
 
         let pointer_pos = ctx.input(|input| input.pointer.interact_pos());
-        self.pointer_used = pointer_pos.map_or(false, |pos| !self.is_in_viewport(pos));
+        self.pointer_used = pointer_pos.is_some_and(|pos| !self.is_in_viewport(pos));
+        self.update_active_viewport(ctx);
 
         self.editor_floating_windows(world, ctx, internal_state);
+        notifications::toasts_ui(ctx, &mut world.resource_mut::<Notifications>());
 
         self.listening_for_text = ctx.wants_keyboard_input();
 
@@ -354,6 +786,77 @@ impl Editor {
         }
     }
 
+    /// Compares the layout with the one of the last frame and sends the [`EditorEvent`]s for opened, closed and focused windows.
+    ///
+    /// This runs at the start of the frame, so that changes made by the ui and by pending commands of the last frame are included.
+    fn send_layout_events(
+        &self,
+        internal_state: &mut EditorInternalState,
+        editor_events: &mut Events<EditorEvent>,
+    ) {
+        let snapshot = LayoutSnapshot::take(
+            &mut internal_state.layout,
+            &internal_state.popped_out_windows,
+        );
+        let Some(previous) = internal_state.layout_snapshot.replace(snapshot) else {
+            return;
+        };
+        let current = internal_state.layout_snapshot.as_ref().unwrap();
+        if previous == *current {
+            return;
+        }
+
+        let name = |window: TypeId| self.windows.get(&window).map_or("", |data| data.name);
+        let (previous_windows, current_windows) = (previous.windows(), current.windows());
+        for &(window, instance) in current_windows.difference(&previous_windows) {
+            editor_events.send(EditorEvent::WindowOpened {
+                window,
+                name: name(window),
+                instance,
+            });
+        }
+        for &(window, instance) in previous_windows.difference(&current_windows) {
+            editor_events.send(EditorEvent::WindowClosed {
+                window,
+                name: name(window),
+                instance,
+            });
+        }
+
+        if current.focused != previous.focused
+            && let Some(TreeTab::CustomWindow(window, instance)) = current.focused
+        {
+            editor_events.send(EditorEvent::WindowFocused {
+                window,
+                name: name(window),
+                instance,
+            });
+        }
+
+        if current.tabs != previous.tabs || current.floating_windows != previous.floating_windows {
+            editor_events.send(EditorEvent::LayoutChanged {
+                preset: internal_state.active_layout_preset.clone(),
+            });
+        }
+    }
+
+    /// Makes the viewport under the pointer active, unless a drag started in another viewport is ongoing
+    fn update_active_viewport(&mut self, ctx: &egui::Context) {
+        let dragging = ctx.input(|input| input.pointer.any_down());
+        let hovered = ctx
+            .input(|input| input.pointer.hover_pos())
+            .and_then(|pos| self.viewport_at(pos));
+        match hovered {
+            Some(viewport) if !dragging => self.active_viewport = viewport,
+            _ if !self.viewports.contains_key(&self.active_viewport) => {
+                if let Some(&viewport) = self.viewports.keys().next() {
+                    self.active_viewport = viewport;
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn editor_menu_bar(
         &mut self,
         world: &mut World,
@@ -370,13 +873,27 @@ impl Editor {
                     });
                 }
 
-                ui.menu_button("Open window", |ui| {
-                    for (&_, window) in self.windows.iter() {
-                        let cx = EditorWindowContext {
-                            window_states: &mut self.window_states,
-                            internal_state,
-                        };
-                        (window.menu_ui_fn)(world, cx, ui);
+                world.resource_scope(|world, menus: Mut<EditorMenus>| {
+                    for (name, items) in menus.iter() {
+                        let is_view = name == "View";
+                        if items.is_empty() && !is_view {
+                            continue;
+                        }
+
+                        ui.menu_button(name, |ui| {
+                            if is_view {
+                                self.view_menu_ui(world, internal_state, ui);
+                                if !items.is_empty() {
+                                    ui.separator();
+                                }
+                            }
+                            menu::menu_items_ui(
+                                world,
+                                items,
+                                ui,
+                                &mut internal_state.pending_commands,
+                            );
+                        });
                     }
                 });
             })
@@ -399,19 +916,70 @@ impl Editor {
         });
     }
 
+    /// Builtin entries of the `View` menu
+    fn view_menu_ui(
+        &mut self,
+        world: &mut World,
+        internal_state: &mut EditorInternalState,
+        ui: &mut egui::Ui,
+    ) {
+        ui.menu_button("Open window", |ui| {
+            for (&_, window) in self.windows.iter() {
+                let cx = EditorWindowContext {
+                    window_states: &mut self.window_states,
+                    internal_state,
+                    instance: 0,
+                    viewport: 0,
+                    viewport_rect: None,
+                };
+                (window.menu_ui_fn)(world, cx, ui);
+            }
+
+            if self.windows.values().any(|window| window.multiple_instances) {
+                ui.separator();
+                ui.menu_button("New instance", |ui| {
+                    for (&type_id, window) in self.windows.iter() {
+                        if window.multiple_instances && ui.button(window.name).clicked() {
+                            internal_state.open_window_instance(type_id);
+                            ui.close_menu();
+                        }
+                    }
+                });
+            }
+        });
+
+        if ui.button("New viewport").clicked() {
+            internal_state.open_viewport();
+            ui.close_menu();
+        }
+        ui.menu_button("Layout", |ui| internal_state.layout_menu_ui(ui));
+    }
+
     fn editor_window_inner(
         &mut self,
         world: &mut World,
         internal_state: &mut EditorInternalState,
         selected: TypeId,
+        instance: u32,
         ui: &mut egui::Ui,
     ) {
+        if instance != 0 {
+            self.swap_instance_state(selected, instance);
+        }
+
         let cx = EditorWindowContext {
             window_states: &mut self.window_states,
             internal_state,
+            instance,
+            viewport: 0,
+            viewport_rect: None,
         };
         let ui_fn = &self.windows.get_mut(&selected).unwrap().ui_fn;
         ui_fn(world, cx, ui);
+
+        if instance != 0 {
+            self.swap_instance_state(selected, instance);
+        }
     }
 
     fn editor_window_context_menu(
@@ -421,17 +989,42 @@ impl Editor {
         tab: TreeTab,
     ) {
         if ui.button("Pop out").clicked() {
-            if let TreeTab::CustomWindow(window) = tab {
+            if let TreeTab::CustomWindow(window, instance) = tab {
                 let id = internal_state.next_floating_window_id();
-                internal_state.floating_windows.push(FloatingWindow {
+                internal_state.layout.floating_windows.push(FloatingWindow {
                     window,
+                    instance,
                     id,
                     initial_position: None,
+                    rect: None,
                 });
             }
 
             ui.close_menu();
         }
+        if let TreeTab::CustomWindow(window, instance) = tab
+            && ui.button("Pop out into new window").clicked()
+        {
+            internal_state.pop_out(window, instance);
+            ui.close_menu();
+        }
+
+        match tab {
+            TreeTab::CustomWindow(window, _) => {
+                if self.windows[&window].multiple_instances
+                    && ui.button("Open new instance").clicked()
+                {
+                    internal_state.open_window_instance(window);
+                    ui.close_menu();
+                }
+            }
+            TreeTab::GameView(_) => {
+                if ui.button("Open new viewport").clicked() {
+                    internal_state.open_viewport();
+                    ui.close_menu();
+                }
+            }
+        }
     }
 
     fn editor_floating_windows(
@@ -441,11 +1034,11 @@ impl Editor {
         internal_state: &mut EditorInternalState,
     ) {
         let mut close_floating_windows = Vec::new();
-        let floating_windows = internal_state.floating_windows.clone();
+        let floating_windows = internal_state.layout.floating_windows.clone();
 
         for (i, floating_window) in floating_windows.into_iter().enumerate() {
             let id = egui::Id::new(floating_window.id);
-            let title = self.windows[&floating_window.window].name;
+            let title = self.window_title(floating_window.window, floating_window.instance);
 
             let mut open = true;
             let default_size = self.windows[&floating_window.window].default_size;
@@ -457,11 +1050,23 @@ impl Editor {
             if let Some(initial_position) = floating_window.initial_position {
                 window = window.default_pos(initial_position - egui::Vec2::new(10.0, 10.0))
             }
-            window.show(ctx, |ui| {
-                self.editor_window_inner(world, internal_state, floating_window.window, ui);
+            if let Some(rect) = floating_window.rect {
+                window = window.default_rect(rect);
+            }
+            let response = window.show(ctx, |ui| {
+                self.editor_window_inner(
+                    world,
+                    internal_state,
+                    floating_window.window,
+                    floating_window.instance,
+                    ui,
+                );
                 let desired_size = (ui.available_size() - (5.0, 5.0).into()).max((0.0, 0.0).into());
                 ui.allocate_space(desired_size);
             });
+            if let Some(response) = response {
+                internal_state.layout.floating_windows[i].rect = Some(response.response.rect);
+            }
 
             if !open {
                 close_floating_windows.push(i);
@@ -469,7 +1074,82 @@ impl Editor {
         }
 
         for &to_remove in close_floating_windows.iter().rev() {
-            let _floating_window = internal_state.floating_windows.swap_remove(to_remove);
+            let _floating_window = internal_state.layout.floating_windows.swap_remove(to_remove);
+        }
+    }
+
+    /// Spawns the OS windows of newly popped out tabs, and docks the tabs of closed ones back
+    fn update_popped_out_windows(world: &mut World) {
+        world.resource_scope(|world, mut internal_state: Mut<EditorInternalState>| {
+            let EditorInternalState {
+                layout,
+                popped_out_windows,
+                ..
+            } = &mut *internal_state;
+
+            popped_out_windows.retain(|popped_out| {
+                let closed = popped_out
+                    .entity
+                    .is_some_and(|entity| world.get::<Window>(entity).is_none());
+                if closed {
+                    let tab = TreeTab::CustomWindow(popped_out.window, popped_out.instance);
+                    layout.state.push_to_focused_leaf(tab);
+                }
+                !closed
+            });
+
+            for popped_out in popped_out_windows
+                .iter_mut()
+                .filter(|popped_out| popped_out.entity.is_none())
+            {
+                let tab = TreeTab::CustomWindow(popped_out.window, popped_out.instance);
+                if let Some(location) = layout.state.find_tab(&tab) {
+                    layout.state.remove_tab(location);
+                }
+                layout.floating_windows.retain(|floating_window| {
+                    floating_window.window != popped_out.window
+                        || floating_window.instance != popped_out.instance
+                });
+
+                let editor = world.resource::<Editor>();
+                let title = editor
+                    .window_title(popped_out.window, popped_out.instance)
+                    .text()
+                    .to_owned();
+                let (width, height) = editor.windows[&popped_out.window].default_size;
+                let window = Window {
+                    title,
+                    resolution: WindowResolution::new(width, height),
+                    ..default()
+                };
+                popped_out.entity = Some(world.spawn((window, PopOutWindow)).id());
+            }
+        });
+    }
+
+    /// Shows the popped out tabs in their OS windows, once bevy_egui added a context to them
+    fn editor_popped_out_windows(
+        &mut self,
+        world: &mut World,
+        internal_state: &mut EditorInternalState,
+    ) {
+        let popped_out_windows: Vec<_> = internal_state
+            .popped_out_windows
+            .iter()
+            .filter_map(|popped_out| {
+                Some((popped_out.window, popped_out.instance, popped_out.entity?))
+            })
+            .collect();
+
+        for (window, instance, entity) in popped_out_windows {
+            let Ok(mut egui_context) = world.query::<&mut EguiContext>().get_mut(world, entity)
+            else {
+                continue;
+            };
+            let ctx = egui_context.get_mut().clone();
+            egui::CentralPanel::default().show(&ctx, |ui| {
+                self.editor_window_inner(world, internal_state, window, instance, ui);
+            });
         }
     }
 
@@ -478,11 +1158,16 @@ impl Editor {
         world: &mut World,
         ui: &mut egui::Ui,
         internal_state: &mut EditorInternalState,
+        viewport: u32,
     ) {
+        let viewport_rect = self.viewport_rect(viewport);
         for (_, window) in self.windows.iter() {
             let cx = EditorWindowContext {
                 window_states: &mut self.window_states,
                 internal_state,
+                instance: 0,
+                viewport,
+                viewport_rect,
             };
 
             (window.viewport_toolbar_ui_fn)(world, cx, ui);
@@ -494,11 +1179,16 @@ impl Editor {
         world: &mut World,
         ui: &mut egui::Ui,
         internal_state: &mut EditorInternalState,
+        viewport: u32,
     ) {
+        let viewport_rect = self.viewport_rect(viewport);
         for (_, window) in self.windows.iter() {
             let cx = EditorWindowContext {
                 window_states: &mut self.window_states,
                 internal_state,
+                instance: 0,
+                viewport,
+                viewport_rect,
             };
 
             (window.viewport_ui_fn)(world, cx, ui);
@@ -516,26 +1206,42 @@ impl egui_dock::TabViewer for TabViewer<'_> {
 
     fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {
         match *tab {
-            TreeTab::GameView => {
-                let viewport = ui.clip_rect();
+            TreeTab::GameView(viewport) => {
+                let rect = ui.clip_rect();
+                // the viewport texture goes below the toolbar and overlays
+                let background = ui.painter().add(egui::Shape::Noop);
+                self.editor.viewports.insert(viewport, rect);
 
                 ui.horizontal(|ui| {
                     ui.style_mut().spacing.button_padding = egui::vec2(2.0, 0.0);
                     let height = ui.spacing().interact_size.y;
                     ui.set_min_size(egui::vec2(ui.available_width(), height));
 
-                    self.editor
-                        .editor_viewport_toolbar_ui(self.world, ui, self.internal_state);
+                    self.editor.editor_viewport_toolbar_ui(
+                        self.world,
+                        ui,
+                        self.internal_state,
+                        viewport,
+                    );
                 });
 
-                self.editor.viewport = viewport;
-
                 self.editor
-                    .editor_viewport_ui(self.world, ui, self.internal_state);
+                    .editor_viewport_ui(self.world, ui, self.internal_state, viewport);
+
+                if let Some(texture) = self.editor.viewport_texture(viewport) {
+                    let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+                    let image = egui::Shape::image(texture, rect, uv, egui::Color32::WHITE);
+                    ui.painter().set(background, image);
+                }
             }
-            TreeTab::CustomWindow(window_id) => {
-                self.editor
-                    .editor_window_inner(self.world, self.internal_state, window_id, ui);
+            TreeTab::CustomWindow(window_id, instance) => {
+                self.editor.editor_window_inner(
+                    self.world,
+                    self.internal_state,
+                    window_id,
+                    instance,
+                    ui,
+                );
             }
         }
     }
@@ -553,15 +1259,23 @@ impl egui_dock::TabViewer for TabViewer<'_> {
 
     fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText {
         match *tab {
-            TreeTab::GameView => "Viewport".into(),
-            TreeTab::CustomWindow(window_id) => {
-                self.editor.windows.get(&window_id).unwrap().name.into()
+            TreeTab::GameView(0) => "Viewport".into(),
+            TreeTab::GameView(viewport) => format!("Viewport ({})", viewport + 1).into(),
+            TreeTab::CustomWindow(window_id, instance) => {
+                self.editor.window_title(window_id, instance)
             }
         }
     }
 
+    fn id(&mut self, tab: &mut Self::Tab) -> egui::Id {
+        match *tab {
+            TreeTab::GameView(viewport) => egui::Id::new("Viewport").with(viewport),
+            TreeTab::CustomWindow(window_id, instance) => egui::Id::new((window_id, instance)),
+        }
+    }
+
     fn clear_background(&self, tab: &Self::Tab) -> bool {
-        !matches!(tab, TreeTab::GameView)
+        !matches!(tab, TreeTab::GameView(_))
     }
 }
 
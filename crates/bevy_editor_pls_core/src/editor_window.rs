@@ -1,6 +1,8 @@
+use bevy::log::{error, warn};
 use bevy::prelude::{App, World};
 use bevy::utils::HashMap;
 use bevy_inspector_egui::egui;
+use serde::{de::DeserializeOwned, Serialize};
 use std::any::{Any, TypeId};
 
 use crate::editor::EditorWindowState;
@@ -10,7 +12,13 @@ pub trait EditorWindow: 'static {
     type State: Default + Any + Send + Sync;
 
     const NAME: &'static str;
+    /// Identifies the window in the persisted layout and window states, so it should not change
+    /// between versions. Defaults to [`NAME`](EditorWindow::NAME); override it if two windows share a name.
+    const ID: &'static str = Self::NAME;
     const DEFAULT_SIZE: (f32, f32) = (0.0, 0.0);
+    /// Whether additional instances of the window, each with their own [`State`](EditorWindow::State),
+    /// can be opened from the `Open window` menu.
+    const MULTIPLE_INSTANCES: bool = false;
 
     fn ui(world: &mut World, cx: EditorWindowContext, ui: &mut egui::Ui);
 
@@ -40,13 +48,72 @@ pub trait EditorWindow: 'static {
     fn app_finish(app: &mut App) {
         let _ = app;
     }
+
+    /// Serializes the part of the [`State`](EditorWindow::State) that should survive a restart.
+    ///
+    /// Persistence is opt-in: by default nothing is saved. The returned string is stored in
+    /// [`EditorPersistence::WINDOWS_FILE`](crate::EditorPersistence::WINDOWS_FILE) on exit and passed to
+    /// [`load_state`](EditorWindow::load_state) on the next start. Only the state of the primary instance is saved.
+    ///
+    /// [`save_ron`] and [`load_ron`] implement this for `serde` types.
+    fn save_state(state: &Self::State) -> Option<String> {
+        let _ = state;
+        None
+    }
+    /// Restores the state saved by [`save_state`](EditorWindow::save_state) in a previous run.
+    fn load_state(state: &mut Self::State, saved: &str) {
+        let _ = (state, saved);
+    }
+}
+
+/// Serializes `value` as RON, for implementing [`EditorWindow::save_state`]
+pub fn save_ron<T: Serialize>(value: &T) -> Option<String> {
+    ron::to_string(value)
+        .map_err(|error| error!("Could not serialize editor window state: {error}"))
+        .ok()
+}
+
+/// Deserializes RON written by [`save_ron`], for implementing [`EditorWindow::load_state`]
+pub fn load_ron<T: DeserializeOwned>(saved: &str) -> Option<T> {
+    ron::from_str(saved)
+        .map_err(|error| warn!("Could not restore editor window state: {error}"))
+        .ok()
 }
 
 pub struct EditorWindowContext<'a> {
     pub(crate) window_states: &'a mut HashMap<TypeId, EditorWindowState>,
     pub(crate) internal_state: &'a mut crate::editor::EditorInternalState,
+    pub(crate) instance: u32,
+    pub(crate) viewport: u32,
+    pub(crate) viewport_rect: Option<egui::Rect>,
 }
 impl EditorWindowContext<'_> {
+    /// The instance of the window currently being drawn, `0` being the primary instance.
+    ///
+    /// While an additional instance is drawn, [`state`](Self::state) and friends return that instance's state for its own window type.
+    pub fn instance(&self) -> u32 {
+        self.instance
+    }
+
+    /// The viewport whose toolbar or overlay is currently being drawn, see [`Editor::viewports`](crate::Editor::viewports).
+    ///
+    /// Always `0` outside of [`EditorWindow::viewport_toolbar_ui`] and [`EditorWindow::viewport_ui`].
+    pub fn viewport(&self) -> u32 {
+        self.viewport
+    }
+
+    /// Rect of the [`viewport`](Self::viewport) in egui coordinates.
+    ///
+    /// `None` outside of [`EditorWindow::viewport_toolbar_ui`] and [`EditorWindow::viewport_ui`].
+    pub fn viewport_rect(&self) -> Option<egui::Rect> {
+        self.viewport_rect
+    }
+
+    /// A painter clipped to the [`viewport_rect`](Self::viewport_rect), for drawing on top of the scene in [`EditorWindow::viewport_ui`]
+    pub fn viewport_painter(&self, ui: &egui::Ui) -> Option<egui::Painter> {
+        self.viewport_rect.map(|rect| ui.painter_at(rect))
+    }
+
     pub fn state_mut<W: EditorWindow>(&mut self) -> Option<&mut W::State> {
         self.window_states
             .get_mut(&TypeId::of::<W>())
@@ -99,6 +166,11 @@ impl EditorWindowContext<'_> {
     pub fn open_floating_window<W: ?Sized + EditorWindow>(&mut self) {
         open_floating_window::<W>(self.internal_state)
     }
+
+    /// Opens a new instance of `W` with a fresh state and returns its instance number
+    pub fn open_floating_window_instance<W: ?Sized + EditorWindow>(&mut self) -> u32 {
+        open_floating_window_instance::<W>(self.internal_state)
+    }
 }
 
 pub fn open_floating_window<W: ?Sized + EditorWindow>(
@@ -107,10 +179,19 @@ pub fn open_floating_window<W: ?Sized + EditorWindow>(
     let floating_window_id = editor_internal_state.next_floating_window_id();
     let window_id = std::any::TypeId::of::<W>();
     editor_internal_state
+        .layout
         .floating_windows
         .push(crate::editor::FloatingWindow {
             window: window_id,
+            instance: 0,
             id: floating_window_id,
             initial_position: None,
+            rect: None,
         });
 }
+
+pub fn open_floating_window_instance<W: ?Sized + EditorWindow>(
+    editor_internal_state: &mut crate::editor::EditorInternalState,
+) -> u32 {
+    editor_internal_state.open_window_instance(TypeId::of::<W>())
+}
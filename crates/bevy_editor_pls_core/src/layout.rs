@@ -0,0 +1,743 @@
+use std::any::TypeId;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::egui;
+use egui_dock::{NodeIndex, SurfaceIndex, TabIndex};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::editor::{Editor, EditorInternalState, FloatingWindow, TreeTab};
+use crate::editor_window::EditorWindow;
+use crate::{EditorSettings, EditorTheme};
+
+/// Where the editor stores files that should survive a restart, like the dock layout and window states.
+#[derive(Resource)]
+pub struct EditorPersistence {
+    /// Directory of the files belonging to the project, i.e. the layout and window states. `None` disables them.
+    pub dir: Option<PathBuf>,
+    /// Directory of the files belonging to the user and shared between projects, i.e. the theme, settings and keymap.
+    /// `None` disables them.
+    pub user_dir: Option<PathBuf>,
+}
+
+impl Default for EditorPersistence {
+    fn default() -> Self {
+        EditorPersistence {
+            dir: Some(PathBuf::from(".bevy_editor_pls")),
+            user_dir: user_config_dir().map(|dir| dir.join("bevy_editor_pls")),
+        }
+    }
+}
+
+impl EditorPersistence {
+    /// Stored in [`dir`](EditorPersistence::dir)
+    pub const LAYOUT_FILE: &'static str = "layout.ron";
+    /// States of the windows implementing [`EditorWindow::save_state`], stored in [`dir`](EditorPersistence::dir)
+    pub const WINDOWS_FILE: &'static str = "windows.ron";
+    /// The [`EditorTheme`](crate::EditorTheme), stored in [`user_dir`](EditorPersistence::user_dir)
+    pub const THEME_FILE: &'static str = "theme.ron";
+    /// The [`EditorSettings`](crate::EditorSettings), stored in [`user_dir`](EditorPersistence::user_dir)
+    pub const SETTINGS_FILE: &'static str = "settings.ron";
+    /// The keyboard and mouse bindings of the editor actions, reloaded when it changes.
+    /// Stored in [`user_dir`](EditorPersistence::user_dir)
+    pub const KEYMAP_FILE: &'static str = "keymap.ron";
+
+    /// The path of `file` inside of [`EditorPersistence::dir`]
+    pub fn path(&self, file: &str) -> Option<PathBuf> {
+        self.dir.as_ref().map(|dir| dir.join(file))
+    }
+
+    /// The path of `file` inside of [`EditorPersistence::user_dir`]
+    pub fn user_path(&self, file: &str) -> Option<PathBuf> {
+        self.user_dir.as_ref().map(|dir| dir.join(file))
+    }
+}
+
+/// The per-user configuration directory of the platform, e.g. `~/.config` on Linux
+fn user_config_dir() -> Option<PathBuf> {
+    let env_dir = |var: &str| {
+        std::env::var_os(var)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+    };
+    if cfg!(target_os = "windows") {
+        env_dir("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME").map(|home| home.join("Library/Application Support"))
+    } else {
+        env_dir("XDG_CONFIG_HOME").or_else(|| env_dir("HOME").map(|home| home.join(".config")))
+    }
+}
+
+#[derive(Debug)]
+pub enum LayoutError {
+    Io(std::io::Error),
+    Serialize(ron::Error),
+    Deserialize(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LayoutError::Io(e) => write!(f, "{e}"),
+            LayoutError::Serialize(e) => write!(f, "failed to serialize: {e}"),
+            LayoutError::Deserialize(e) => write!(f, "failed to parse: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+/// An arrangement of docked and floating editor windows.
+///
+/// The layout currently shown by the editor can be accessed via [`EditorInternalState::layout`],
+/// and named layouts can be registered as presets via [`EditorInternalState::add_layout_preset`].
+#[derive(Clone)]
+pub struct Layout {
+    pub(crate) state: egui_dock::DockState<TreeTab>,
+    pub(crate) floating_windows: Vec<FloatingWindow>,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Layout {
+            state: egui_dock::DockState::new(vec![TreeTab::GameView(0)]),
+            floating_windows: Vec::new(),
+        }
+    }
+}
+
+impl Layout {
+    /// A layout containing only the game viewport
+    pub fn new() -> Self {
+        Layout::default()
+    }
+
+    pub fn push_to_focused_leaf<W: EditorWindow>(&mut self) {
+        self.state
+            .push_to_focused_leaf(TreeTab::CustomWindow(TypeId::of::<W>(), 0));
+        if let Some((surface_index, node_index)) = self.state.focused_leaf() {
+            self.state
+                .set_active_tab((surface_index, node_index, TabIndex(0)));
+        };
+    }
+
+    pub fn split<W: EditorWindow>(
+        &mut self,
+        parent: NodeIndex,
+        split: egui_dock::Split,
+        fraction: f32,
+    ) -> [NodeIndex; 2] {
+        let node = egui_dock::Node::leaf(TreeTab::CustomWindow(TypeId::of::<W>(), 0));
+        self.state
+            .split((SurfaceIndex::main(), parent), split, fraction, node)
+    }
+
+    pub fn split_right<W: EditorWindow>(
+        &mut self,
+        parent: NodeIndex,
+        fraction: f32,
+    ) -> [NodeIndex; 2] {
+        self.split::<W>(parent, egui_dock::Split::Right, fraction)
+    }
+    pub fn split_left<W: EditorWindow>(
+        &mut self,
+        parent: NodeIndex,
+        fraction: f32,
+    ) -> [NodeIndex; 2] {
+        self.split::<W>(parent, egui_dock::Split::Left, fraction)
+    }
+    pub fn split_above<W: EditorWindow>(
+        &mut self,
+        parent: NodeIndex,
+        fraction: f32,
+    ) -> [NodeIndex; 2] {
+        self.split::<W>(parent, egui_dock::Split::Above, fraction)
+    }
+    pub fn split_below<W: EditorWindow>(
+        &mut self,
+        parent: NodeIndex,
+        fraction: f32,
+    ) -> [NodeIndex; 2] {
+        self.split::<W>(parent, egui_dock::Split::Below, fraction)
+    }
+
+    pub fn split_many(
+        &mut self,
+        parent: NodeIndex,
+        fraction: f32,
+        split: egui_dock::Split,
+        windows: &[TypeId],
+    ) -> [NodeIndex; 2] {
+        let tabs = windows
+            .iter()
+            .map(|&window| TreeTab::CustomWindow(window, 0))
+            .collect();
+        let node = egui_dock::Node::leaf_with(tabs);
+        self.state
+            .split((SurfaceIndex::main(), parent), split, fraction, node)
+    }
+
+    /// Show `W` as a floating window when this layout is active
+    pub fn add_floating_window<W: EditorWindow>(&mut self) {
+        self.floating_windows.push(FloatingWindow {
+            window: TypeId::of::<W>(),
+            instance: 0,
+            // assigned when the layout gets activated
+            id: 0,
+            initial_position: None,
+            rect: None,
+        });
+    }
+
+    pub(crate) fn contains_window_instance(&self, window: TypeId, instance: u32) -> bool {
+        let in_dock = self.state.iter_all_tabs().any(|(_, tab)| {
+            matches!(*tab, TreeTab::CustomWindow(w, i) if w == window && i == instance)
+        });
+        in_dock
+            || self
+                .floating_windows
+                .iter()
+                .any(|floating| floating.window == window && floating.instance == instance)
+    }
+
+    pub fn contains_viewport(&self, viewport: u32) -> bool {
+        self.state
+            .iter_all_tabs()
+            .any(|(_, tab)| *tab == TreeTab::GameView(viewport))
+    }
+
+    /// Removes the docked and floating instances of the windows for which `keep` returns `false`
+    pub(crate) fn retain_windows(&mut self, keep: impl Fn(TypeId) -> bool) {
+        // `DockState::retain_tabs` also empties leaves whose child slots are unused,
+        // so the tabs are removed one at a time instead
+        let removed: Vec<TreeTab> = self
+            .state
+            .iter_all_tabs()
+            .filter_map(|(_, tab)| match *tab {
+                TreeTab::CustomWindow(window, _) if !keep(window) => Some(*tab),
+                _ => None,
+            })
+            .collect();
+        for tab in removed {
+            if let Some(location) = self.state.find_tab(&tab) {
+                self.state.remove_tab(location);
+            }
+        }
+        self.floating_windows
+            .retain(|floating_window| keep(floating_window.window));
+    }
+
+    /// Largest window instance or viewport id used in this layout
+    pub(crate) fn max_window_instance(&self) -> u32 {
+        let dock_instances = self.state.iter_all_tabs().map(|(_, tab)| match *tab {
+            TreeTab::GameView(viewport) => viewport,
+            TreeTab::CustomWindow(_, instance) => instance,
+        });
+        let floating_instances = self.floating_windows.iter().map(|floating| floating.instance);
+        dock_instances.chain(floating_instances).max().unwrap_or(0)
+    }
+}
+
+/// Declarative description of the docked windows of a [`Layout`], built with [`Layout::from_dock`].
+///
+/// Splits are nested from the inside out, like the calls to [`Layout::split`]:
+/// ```rust,ignore
+/// let dock = DockNode::Viewport
+///     .split_left(0.2, DockNode::window::<HierarchyWindow>())
+///     .split_right(0.75, DockNode::window::<InspectorWindow>());
+/// ```
+#[derive(Clone, Debug)]
+pub enum DockNode {
+    /// The primary game viewport
+    Viewport,
+    /// Tabs of the given windows, where the first one is active
+    Tabs(Vec<TypeId>),
+    /// `node` split with a new `other` node, as in [`egui_dock::DockState::split`].
+    ///
+    /// `fraction` is the share of the left or upper side.
+    Split {
+        node: Box<DockNode>,
+        split: egui_dock::Split,
+        fraction: f32,
+        other: Box<DockNode>,
+    },
+}
+
+impl DockNode {
+    pub fn window<W: EditorWindow>() -> Self {
+        DockNode::Tabs(vec![TypeId::of::<W>()])
+    }
+
+    pub fn tabs(windows: &[TypeId]) -> Self {
+        DockNode::Tabs(windows.to_vec())
+    }
+
+    pub fn split(self, split: egui_dock::Split, fraction: f32, other: DockNode) -> Self {
+        DockNode::Split {
+            node: Box::new(self),
+            split,
+            fraction,
+            other: Box::new(other),
+        }
+    }
+
+    pub fn split_right(self, fraction: f32, other: DockNode) -> Self {
+        self.split(egui_dock::Split::Right, fraction, other)
+    }
+    pub fn split_left(self, fraction: f32, other: DockNode) -> Self {
+        self.split(egui_dock::Split::Left, fraction, other)
+    }
+    pub fn split_above(self, fraction: f32, other: DockNode) -> Self {
+        self.split(egui_dock::Split::Above, fraction, other)
+    }
+    pub fn split_below(self, fraction: f32, other: DockNode) -> Self {
+        self.split(egui_dock::Split::Below, fraction, other)
+    }
+
+    /// Removes the windows for which `keep` returns `false`.
+    ///
+    /// Nodes left without tabs are removed, and so are splits with only one remaining side.
+    pub fn retain_windows(self, keep: &impl Fn(TypeId) -> bool) -> Option<DockNode> {
+        match self {
+            DockNode::Viewport => Some(DockNode::Viewport),
+            DockNode::Tabs(mut windows) => {
+                windows.retain(|&window| keep(window));
+                (!windows.is_empty()).then_some(DockNode::Tabs(windows))
+            }
+            DockNode::Split {
+                node,
+                split,
+                fraction,
+                other,
+            } => match (node.retain_windows(keep), other.retain_windows(keep)) {
+                (Some(node), Some(other)) => Some(node.split(split, fraction, other)),
+                (node, other) => node.or(other),
+            },
+        }
+    }
+
+    /// Tabs of the leaf which ends up at the position of this node when it gets split
+    fn first_tabs(&self) -> Vec<TreeTab> {
+        match self {
+            DockNode::Viewport => vec![TreeTab::GameView(0)],
+            DockNode::Tabs(windows) => windows
+                .iter()
+                .map(|&window| TreeTab::CustomWindow(window, 0))
+                .collect(),
+            DockNode::Split { node, .. } => node.first_tabs(),
+        }
+    }
+
+    /// Splits the leaf at `index`, which contains the [`DockNode::first_tabs`] of this node
+    fn build(&self, state: &mut egui_dock::DockState<TreeTab>, index: NodeIndex) {
+        let DockNode::Split {
+            node,
+            split,
+            fraction,
+            other,
+        } = self
+        else {
+            return;
+        };
+        let new = egui_dock::Node::leaf_with(other.first_tabs());
+        let [node_index, other_index] =
+            state.split((SurfaceIndex::main(), index), *split, *fraction, new);
+        other.build(state, other_index);
+        node.build(state, node_index);
+    }
+}
+
+impl Layout {
+    /// A layout with the docked windows described by `dock`
+    pub fn from_dock(dock: &DockNode) -> Self {
+        let mut state = egui_dock::DockState::new(dock.first_tabs());
+        dock.build(&mut state, NodeIndex::root());
+        Layout {
+            state,
+            floating_windows: Vec::new(),
+        }
+    }
+}
+
+impl EditorInternalState {
+    /// Register a named layout preset, which can be switched to from the `Layout` menu.
+    ///
+    /// Replaces any existing preset of the same name.
+    pub fn add_layout_preset(&mut self, name: impl Into<String>, layout: Layout) {
+        self.layout_presets.insert(name.into(), layout);
+    }
+
+    pub fn remove_layout_preset(&mut self, name: &str) -> Option<Layout> {
+        if self.active_layout_preset.as_deref() == Some(name) {
+            self.active_layout_preset = None;
+        }
+        self.layout_presets.shift_remove(name)
+    }
+
+    /// Drops windows which were never added to the editor from the layout and the presets
+    pub(crate) fn retain_registered_windows(&mut self, editor: &Editor) {
+        let registered = |window| editor.window_id(window).is_some();
+        self.layout.retain_windows(registered);
+        for preset in self.layout_presets.values_mut() {
+            preset.retain_windows(registered);
+        }
+    }
+
+    pub fn layout_presets(&self) -> impl Iterator<Item = &str> {
+        self.layout_presets.keys().map(String::as_str)
+    }
+
+    pub fn active_layout_preset(&self) -> Option<&str> {
+        self.active_layout_preset.as_deref()
+    }
+
+    /// Store the current layout as the preset `name` and mark it as active.
+    pub fn save_layout_preset(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        self.layout_presets.insert(name.clone(), self.layout.clone());
+        self.active_layout_preset = Some(name);
+    }
+
+    /// Replace the docked and floating windows with the ones of the preset `name`.
+    ///
+    /// Changes made to the previously active preset are kept, so switching back restores them.
+    /// Returns `false` if no such preset exists.
+    pub fn switch_layout_preset(&mut self, name: &str) -> bool {
+        let Some(mut layout) = self.layout_presets.get(name).cloned() else {
+            return false;
+        };
+        for floating_window in &mut layout.floating_windows {
+            floating_window.id = self.next_floating_window_id();
+        }
+
+        let previous = std::mem::replace(&mut self.layout, layout);
+        if let Some(previous_preset) = self
+            .active_layout_preset
+            .as_ref()
+            .and_then(|active| self.layout_presets.get_mut(active))
+        {
+            *previous_preset = previous;
+        }
+        self.active_layout_preset = Some(name.to_owned());
+
+        true
+    }
+
+    pub(crate) fn layout_menu_ui(&mut self, ui: &mut egui::Ui) {
+        let mut switch_to = None;
+        let mut remove = None;
+        for name in self.layout_presets.keys() {
+            ui.horizontal(|ui| {
+                let active = self.active_layout_preset.as_ref() == Some(name);
+                if ui.radio(active, name.as_str()).clicked() && !active {
+                    switch_to = Some(name.clone());
+                    ui.close_menu();
+                }
+                if ui.small_button("🗑").on_hover_text("Remove preset").clicked() {
+                    remove = Some(name.clone());
+                }
+            });
+        }
+        if let Some(name) = switch_to {
+            self.switch_layout_preset(&name);
+        }
+        if let Some(name) = remove {
+            self.remove_layout_preset(&name);
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            egui::TextEdit::singleline(&mut self.new_layout_preset_name)
+                .hint_text("Preset name")
+                .desired_width(120.0)
+                .show(ui);
+            let name = self.new_layout_preset_name.trim();
+            if ui
+                .add_enabled(!name.is_empty(), egui::Button::new("Save current"))
+                .clicked()
+            {
+                let name = name.to_owned();
+                self.save_layout_preset(name);
+                self.new_layout_preset_name.clear();
+                ui.close_menu();
+            }
+        });
+    }
+}
+
+/// Tab of the dock tree, with windows referred to by their stable id instead of their [`TypeId`].
+#[derive(Serialize, Deserialize)]
+enum SavedTab {
+    GameView,
+    Window(String),
+    WindowInstance(String, u32),
+    Viewport(u32),
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedFloatingWindow {
+    window: String,
+    #[serde(default)]
+    instance: u32,
+    rect: Option<egui::Rect>,
+}
+
+/// Serializable form of a [`Layout`]
+#[derive(Serialize, Deserialize)]
+struct SavedLayout {
+    dock: egui_dock::DockState<SavedTab>,
+    floating_windows: Vec<SavedFloatingWindow>,
+}
+
+impl SavedLayout {
+    fn from_layout(editor: &Editor, layout: &Layout) -> Self {
+        let dock = layout.state.filter_map_tabs(|tab| match *tab {
+            TreeTab::GameView(0) => Some(SavedTab::GameView),
+            TreeTab::GameView(viewport) => Some(SavedTab::Viewport(viewport)),
+            TreeTab::CustomWindow(type_id, 0) => {
+                Some(SavedTab::Window(editor.window_id(type_id)?.to_owned()))
+            }
+            TreeTab::CustomWindow(type_id, instance) => Some(SavedTab::WindowInstance(
+                editor.window_id(type_id)?.to_owned(),
+                instance,
+            )),
+        });
+        let floating_windows = layout
+            .floating_windows
+            .iter()
+            .filter_map(|floating_window| {
+                Some(SavedFloatingWindow {
+                    window: editor.window_id(floating_window.window)?.to_owned(),
+                    instance: floating_window.instance,
+                    rect: floating_window.rect,
+                })
+            })
+            .collect();
+
+        SavedLayout {
+            dock,
+            floating_windows,
+        }
+    }
+
+    /// Windows which are no longer registered are dropped from the layout.
+    fn to_layout(&self, editor: &Editor) -> Layout {
+        let state = self.dock.filter_map_tabs(|tab| match tab {
+            SavedTab::GameView => Some(TreeTab::GameView(0)),
+            SavedTab::Viewport(viewport) => Some(TreeTab::GameView(*viewport)),
+            SavedTab::Window(id) => editor
+                .window_by_id(id)
+                .map(|window| TreeTab::CustomWindow(window, 0)),
+            SavedTab::WindowInstance(id, instance) => editor
+                .window_by_id(id)
+                .map(|window| TreeTab::CustomWindow(window, *instance)),
+        });
+        let floating_windows = self
+            .floating_windows
+            .iter()
+            .filter_map(|saved| {
+                Some(FloatingWindow {
+                    window: editor.window_by_id(&saved.window)?,
+                    instance: saved.instance,
+                    id: 0,
+                    initial_position: None,
+                    rect: saved.rect,
+                })
+            })
+            .collect();
+
+        Layout {
+            state,
+            floating_windows,
+        }
+    }
+}
+
+/// Everything persisted to [`EditorPersistence::LAYOUT_FILE`]
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SavedLayouts {
+    current: SavedLayout,
+    #[serde(default)]
+    active_preset: Option<String>,
+    #[serde(default)]
+    presets: Vec<(String, SavedLayout)>,
+}
+
+impl SavedLayouts {
+    pub(crate) fn from_state(editor: &Editor, internal_state: &EditorInternalState) -> Self {
+        // popped out windows are closed on exit, so they are saved docked back
+        let mut current = internal_state.layout.clone();
+        for popped_out in &internal_state.popped_out_windows {
+            let tab = TreeTab::CustomWindow(popped_out.window, popped_out.instance);
+            current.state.push_to_focused_leaf(tab);
+        }
+
+        SavedLayouts {
+            current: SavedLayout::from_layout(editor, &current),
+            active_preset: internal_state.active_layout_preset.clone(),
+            presets: internal_state
+                .layout_presets
+                .iter()
+                .map(|(name, layout)| (name.clone(), SavedLayout::from_layout(editor, layout)))
+                .collect(),
+        }
+    }
+
+    /// Presets registered in code are kept, unless the saved file contains a preset of the same name.
+    pub(crate) fn apply(&self, editor: &Editor, internal_state: &mut EditorInternalState) {
+        let mut layout = self.current.to_layout(editor);
+        for floating_window in &mut layout.floating_windows {
+            floating_window.id = internal_state.next_floating_window_id();
+        }
+        internal_state.layout = layout;
+
+        for (name, preset) in &self.presets {
+            internal_state.add_layout_preset(name.clone(), preset.to_layout(editor));
+        }
+        internal_state.active_layout_preset = self
+            .active_preset
+            .clone()
+            .filter(|name| internal_state.layout_presets.contains_key(name));
+        internal_state.reserve_window_instances();
+    }
+
+    pub(crate) fn load(path: &Path) -> Result<Self, LayoutError> {
+        read_ron(path)
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> Result<(), LayoutError> {
+        write_ron(self, path)
+    }
+}
+
+/// Reads a value from the RON file at `path`, used for all files of [`EditorPersistence`]
+pub fn read_ron<T: DeserializeOwned>(path: &Path) -> Result<T, LayoutError> {
+    let contents = std::fs::read_to_string(path).map_err(LayoutError::Io)?;
+    ron::from_str(&contents).map_err(LayoutError::Deserialize)
+}
+
+/// Writes `value` as pretty RON to `path`, creating the parent directories if necessary
+pub fn write_ron<T: Serialize>(value: &T, path: &Path) -> Result<(), LayoutError> {
+    let contents = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(LayoutError::Serialize)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(LayoutError::Io)?;
+    }
+    std::fs::write(path, contents).map_err(LayoutError::Io)
+}
+
+impl Editor {
+    /// Write the states of all windows implementing [`EditorWindow::save_state`] to `path`.
+    pub fn save_window_states_to(&self, path: &Path) -> Result<(), LayoutError> {
+        write_ron(&self.save_window_states(), path)
+    }
+
+    /// Restore the window states saved at `path`.
+    pub fn load_window_states_from(&mut self, path: &Path) -> Result<(), LayoutError> {
+        let saved: BTreeMap<String, String> = read_ron(path)?;
+        self.load_window_states(&saved);
+        Ok(())
+    }
+}
+
+impl EditorInternalState {
+    /// Write the current dock layout, floating windows and layout presets to `path`.
+    pub fn save_layout(&self, editor: &Editor, path: &Path) -> Result<(), LayoutError> {
+        SavedLayouts::from_state(editor, self).save(path)
+    }
+
+    /// Replace the current dock layout, floating windows and layout presets with the ones saved at `path`.
+    pub fn load_layout(&mut self, editor: &Editor, path: &Path) -> Result<(), LayoutError> {
+        let layouts = SavedLayouts::load(path)?;
+        layouts.apply(editor, self);
+        Ok(())
+    }
+}
+
+/// Restores the layout saved by a previous run, if there is one.
+pub(crate) fn restore_layout(world: &mut World) {
+    let Some(path) = world
+        .get_resource::<EditorPersistence>()
+        .and_then(|persistence| persistence.path(EditorPersistence::LAYOUT_FILE))
+    else {
+        return;
+    };
+    if !path.exists() {
+        return;
+    }
+
+    world.resource_scope(|world, mut internal_state: Mut<EditorInternalState>| {
+        let editor = world.resource::<Editor>();
+        match internal_state.load_layout(editor, &path) {
+            Ok(()) => debug!("Restored editor layout from {}", path.display()),
+            Err(error) => warn!("Could not restore editor layout from {}: {error}", path.display()),
+        }
+    });
+}
+
+/// Restores the window states saved by a previous run, if there are any.
+pub(crate) fn restore_window_states(world: &mut World) {
+    let Some(path) = world
+        .get_resource::<EditorPersistence>()
+        .and_then(|persistence| persistence.path(EditorPersistence::WINDOWS_FILE))
+    else {
+        return;
+    };
+    if !path.exists() {
+        return;
+    }
+
+    let mut editor = world.resource_mut::<Editor>();
+    match editor.load_window_states_from(&path) {
+        Ok(()) => debug!("Restored editor window states from {}", path.display()),
+        Err(error) => warn!(
+            "Could not restore editor window states from {}: {error}",
+            path.display()
+        ),
+    }
+}
+
+pub(crate) fn save_on_exit(
+    mut exit_events: EventReader<AppExit>,
+    editor: Res<Editor>,
+    internal_state: Res<EditorInternalState>,
+    theme: Res<EditorTheme>,
+    settings: Res<EditorSettings>,
+    persistence: Res<EditorPersistence>,
+) {
+    if exit_events.read().last().is_none() {
+        return;
+    }
+
+    if let Some(path) = persistence.path(EditorPersistence::LAYOUT_FILE)
+        && let Err(error) = internal_state.save_layout(&editor, &path)
+    {
+        error!(
+            "Could not save editor layout to {}: {error}",
+            path.display()
+        );
+    }
+    if let Some(path) = persistence.path(EditorPersistence::WINDOWS_FILE)
+        && let Err(error) = editor.save_window_states_to(&path)
+    {
+        error!(
+            "Could not save editor window states to {}: {error}",
+            path.display()
+        );
+    }
+    if let Some(path) = persistence.user_path(EditorPersistence::THEME_FILE)
+        && let Err(error) = theme.save(&path)
+    {
+        error!("Could not save editor theme to {}: {error}", path.display());
+    }
+    if let Some(path) = persistence.user_path(EditorPersistence::SETTINGS_FILE)
+        && let Err(error) = settings.save(&path)
+    {
+        error!(
+            "Could not save editor settings to {}: {error}",
+            path.display()
+        );
+    }
+}
@@ -2,6 +2,27 @@
 pub mod editor;
 /// Trait definition for new editor windows
 pub mod editor_window;
+/// Saving and restoring the dock layout
+pub mod layout;
+/// Menus of the editor menu bar
+pub mod menu;
+/// Toasts and the history of editor messages
+pub mod notifications;
+/// Fuzzy searchable command palette
+pub mod palette;
+/// Run conditions depending on the state of the editor
+pub mod run_conditions;
+/// Editor-wide settings of the builtin windows and editor cameras
+pub mod settings;
+/// Status bar at the bottom of the editor
+pub mod status_bar;
+/// Colors, text size and scale of the editor ui
+pub mod theme;
+/// Undo/redo history of changes made through the editor
+pub mod undo;
+/// Headless harness for testing editor windows
+#[cfg(feature = "testing")]
+pub mod testing;
 
 use std::marker::PhantomData;
 
@@ -16,7 +37,14 @@ use bevy_inspector_egui::{
 use editor::EditorInternalState;
 use editor_window::EditorWindow;
 
-pub use editor::{Editor, EditorEvent};
+pub use editor::{Editor, EditorEvent, PopOutWindow};
+pub use layout::EditorPersistence;
+pub use menu::AddMenuItem;
+pub use notifications::{Notification, Notifications};
+pub use palette::AddPaletteCommand;
+pub use settings::EditorSettings;
+pub use status_bar::AddStatusBarItem;
+pub use theme::EditorTheme;
 
 /// Re-export of [`egui_dock`]
 pub use egui_dock;
@@ -52,6 +80,21 @@ impl AddEditorWindow for App {
 pub enum EditorSet {
     /// In [`CoreSet::PostUpdate`]
     UI,
+    /// Systems which only run while the editor is shown, like gizmos and overlays.
+    ///
+    /// Configured in [`PreUpdate`], [`Update`] and [`PostUpdate`].
+    Active,
+    /// Systems which only run while the editor is hidden and the game is played.
+    ///
+    /// Configured in [`PreUpdate`], [`Update`] and [`PostUpdate`].
+    Inactive,
+}
+
+pub(crate) fn editor_state_sets() -> impl IntoSystemSetConfigs {
+    (
+        EditorSet::Active.run_if(run_conditions::editor_active()),
+        EditorSet::Inactive.run_if(run_conditions::editor_inactive()),
+    )
 }
 
 pub struct EditorPlugin {
@@ -77,17 +120,53 @@ impl Plugin for EditorPlugin {
             WindowRef::Entity(entity) => (entity, true),
         };
 
-        app.insert_resource(Editor::new(window_entity, always_active))
-            .init_resource::<EditorInternalState>()
-            .add_event::<EditorEvent>()
-            .configure_sets(PostUpdate, EditorSet::UI)
-            .add_systems(
-                Update,
-                Editor::system
-                    .in_set(EditorSet::UI)
-                    .before(TransformSystem::TransformPropagate)
-                    .before(CameraUpdateSystem)
-                    .before(EguiSet::ProcessOutput),
-            );
+        setup_editor(app, Editor::new(window_entity, always_active));
+        app.add_systems(
+            Update,
+            Editor::system
+                .in_set(EditorSet::UI)
+                .before(TransformSystem::TransformPropagate)
+                .before(CameraUpdateSystem)
+                .before(EguiSet::ProcessOutput),
+        )
+        .add_systems(Last, layout::save_on_exit);
+    }
+
+    fn finish(&self, app: &mut App) {
+        finish_editor(app.world_mut());
     }
 }
+
+/// Resources, events and systems shared by the [`EditorPlugin`] and the test harness
+pub(crate) fn setup_editor(app: &mut App, editor: Editor) {
+    app.insert_resource(editor)
+        .init_resource::<EditorInternalState>()
+        .init_resource::<EditorPersistence>()
+        .init_resource::<undo::UndoStack>()
+        .init_resource::<palette::PaletteCommands>()
+        .init_resource::<menu::EditorMenus>()
+        .init_resource::<Notifications>()
+        .init_resource::<status_bar::StatusBar>()
+        .add_event::<EditorEvent>()
+        .add_event::<Notification>()
+        .configure_sets(PostUpdate, EditorSet::UI)
+        .configure_sets(PreUpdate, editor_state_sets())
+        .configure_sets(Update, editor_state_sets())
+        .configure_sets(PostUpdate, editor_state_sets())
+        .add_systems(
+            Update,
+            (notifications::collect_notifications, theme::apply_theme).before(EditorSet::UI),
+        );
+}
+
+/// Restores the persisted editor state, once all windows are registered
+pub(crate) fn finish_editor(world: &mut World) {
+    world.resource_scope(|world, mut internal_state: Mut<EditorInternalState>| {
+        internal_state.retain_registered_windows(world.resource::<Editor>());
+    });
+    layout::restore_layout(world);
+    layout::restore_window_states(world);
+    // the theme and settings are only inserted now, so that the ones inserted by the app take precedence
+    theme::restore_theme(world);
+    settings::restore_settings(world);
+}
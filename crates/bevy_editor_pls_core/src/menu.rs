@@ -0,0 +1,260 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::egui;
+
+use crate::editor::{CommandFn, EditorInternalState};
+use crate::undo::UndoStack;
+
+type MenuUiFn = Box<dyn Fn(&mut World, &mut egui::Ui) + Send + Sync>;
+type EnabledFn = Box<dyn Fn(&World) -> bool + Send + Sync>;
+
+enum MenuItemKind {
+    Button {
+        label: Cow<'static, str>,
+        shortcut: Option<Cow<'static, str>>,
+        run: CommandFn,
+    },
+    Separator,
+    Ui(MenuUiFn),
+}
+
+/// An entry of a menu in the editor menu bar, registered with [`AddMenuItem::add_menu_item`].
+///
+/// ```rust,ignore
+/// app.add_menu_item(
+///     "Tools",
+///     MenuItem::new("Respawn player", respawn_player)
+///         .shortcut("Ctrl+R")
+///         .enabled_when(|world: &World| world.contains_resource::<Level>()),
+/// );
+/// ```
+pub struct MenuItem {
+    kind: MenuItemKind,
+    order: i32,
+    enabled_fn: Option<EnabledFn>,
+    enabled: bool,
+}
+
+impl MenuItem {
+    fn from_kind(kind: MenuItemKind) -> Self {
+        MenuItem {
+            kind,
+            order: 0,
+            enabled_fn: None,
+            enabled: true,
+        }
+    }
+
+    /// A button running `run` when clicked.
+    ///
+    /// `run` is called after the editor ui has been drawn, so it can access every resource.
+    pub fn new(
+        label: impl Into<Cow<'static, str>>,
+        run: impl Fn(&mut World) + Send + Sync + 'static,
+    ) -> Self {
+        MenuItem::from_kind(MenuItemKind::Button {
+            label: label.into(),
+            shortcut: None,
+            run: Arc::new(run),
+        })
+    }
+
+    pub fn separator() -> Self {
+        MenuItem::from_kind(MenuItemKind::Separator)
+    }
+
+    /// Arbitrary ui, e.g. a checkbox or a submenu.
+    ///
+    /// Unlike [`MenuItem::new`], this is drawn while the editor resources are in use.
+    pub fn ui(ui: impl Fn(&mut World, &mut egui::Ui) + Send + Sync + 'static) -> Self {
+        MenuItem::from_kind(MenuItemKind::Ui(Box::new(ui)))
+    }
+
+    /// Text shown next to the label, like `Ctrl+S`. This does not bind the shortcut.
+    pub fn shortcut(mut self, shortcut: impl Into<Cow<'static, str>>) -> Self {
+        if let MenuItemKind::Button {
+            shortcut: ref mut item_shortcut,
+            ..
+        } = self.kind
+        {
+            *item_shortcut = Some(shortcut.into());
+        }
+        self
+    }
+
+    /// Show the item disabled when `enabled` returns false. It is evaluated once per frame.
+    pub fn enabled_when(
+        mut self,
+        enabled: impl Fn(&World) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.enabled_fn = Some(Box::new(enabled));
+        self
+    }
+
+    /// Items are sorted by ascending order, and by registration order within the same order.
+    pub fn order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+}
+
+struct Menu {
+    name: Cow<'static, str>,
+    order: i32,
+    items: Vec<MenuItem>,
+}
+
+/// Top-level menus of the editor menu bar and their items.
+///
+/// `File`, `Edit`, `View` and `Tools` exist by default. `View` also contains the `Open window` and `Layout` submenus.
+#[derive(Resource)]
+pub struct EditorMenus {
+    menus: Vec<Menu>,
+}
+
+impl Default for EditorMenus {
+    fn default() -> Self {
+        let mut menus = EditorMenus { menus: Vec::new() };
+        menus.add_menu("File", 0);
+        menus.add_menu("Edit", 10);
+        menus.add_menu("View", 20);
+        menus.add_menu("Tools", 30);
+
+        menus.add_item("Edit", MenuItem::ui(undo_ui).order(-20));
+        menus.add_item("Edit", MenuItem::ui(redo_ui).order(-20));
+        menus.add_item("Edit", MenuItem::separator().order(-10));
+        menus.add_item(
+            "Edit",
+            MenuItem::new("Command palette", |world: &mut World| {
+                world
+                    .resource_mut::<EditorInternalState>()
+                    .open_command_palette();
+            })
+            .shortcut("Ctrl+Shift+P")
+            .order(100),
+        );
+
+        menus
+    }
+}
+
+impl EditorMenus {
+    /// Adds a top-level menu, or changes the order of an existing one.
+    /// Menus are sorted by ascending order.
+    pub fn add_menu(&mut self, name: impl Into<Cow<'static, str>>, order: i32) {
+        let name = name.into();
+        let menu = match self.menus.iter().position(|menu| menu.name == name) {
+            Some(i) => Menu {
+                order,
+                ..self.menus.remove(i)
+            },
+            None => Menu {
+                name,
+                order,
+                items: Vec::new(),
+            },
+        };
+        let index = self.menus.partition_point(|other| other.order <= order);
+        self.menus.insert(index, menu);
+    }
+
+    /// Adds `item` to `menu`. Menus that don't exist yet are added after the default menus.
+    pub fn add_item(&mut self, menu: impl Into<Cow<'static, str>>, item: MenuItem) {
+        let name = menu.into();
+        if !self.menus.iter().any(|menu| menu.name == name) {
+            self.add_menu(name.clone(), 100);
+        }
+        let menu = self
+            .menus
+            .iter_mut()
+            .find(|menu| menu.name == name)
+            .unwrap();
+
+        let index = menu.items.partition_point(|other| other.order <= item.order);
+        menu.items.insert(index, item);
+    }
+
+    pub(crate) fn update_enabled(world: &mut World) {
+        world.resource_scope(|world, mut menus: Mut<EditorMenus>| {
+            for menu in &mut menus.menus {
+                for item in &mut menu.items {
+                    item.enabled = item.enabled_fn.as_ref().is_none_or(|f| f(world));
+                }
+            }
+        });
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &[MenuItem])> {
+        self.menus
+            .iter()
+            .map(|menu| (menu.name.as_ref(), menu.items.as_slice()))
+    }
+}
+
+pub(crate) fn menu_items_ui(
+    world: &mut World,
+    items: &[MenuItem],
+    ui: &mut egui::Ui,
+    pending_commands: &mut Vec<CommandFn>,
+) {
+    for item in items {
+        match &item.kind {
+            MenuItemKind::Button {
+                label,
+                shortcut,
+                run,
+            } => {
+                let mut button = egui::Button::new(label.as_ref());
+                if let Some(shortcut) = shortcut {
+                    button = button.shortcut_text(shortcut.as_ref());
+                }
+                if ui.add_enabled(item.enabled, button).clicked() {
+                    pending_commands.push(Arc::clone(run));
+                    ui.close_menu();
+                }
+            }
+            MenuItemKind::Separator => {
+                ui.separator();
+            }
+            MenuItemKind::Ui(menu_ui) => {
+                ui.add_enabled_ui(item.enabled, |ui| menu_ui(world, ui));
+            }
+        }
+    }
+}
+
+/// Extension trait for [`App`] to add items to the editor menu bar
+pub trait AddMenuItem {
+    fn add_menu_item(&mut self, menu: impl Into<Cow<'static, str>>, item: MenuItem) -> &mut Self;
+}
+
+impl AddMenuItem for App {
+    fn add_menu_item(&mut self, menu: impl Into<Cow<'static, str>>, item: MenuItem) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(EditorMenus::default)
+            .add_item(menu, item);
+        self
+    }
+}
+
+fn undo_ui(world: &mut World, ui: &mut egui::Ui) {
+    let undo = world.resource::<UndoStack>().undo_description();
+    let text = undo.as_ref().map_or("Undo".into(), |d| format!("Undo {d}"));
+    let button = egui::Button::new(text).shortcut_text("Ctrl+Z");
+    if ui.add_enabled(undo.is_some(), button).clicked() {
+        UndoStack::undo(world);
+        ui.close_menu();
+    }
+}
+
+fn redo_ui(world: &mut World, ui: &mut egui::Ui) {
+    let redo = world.resource::<UndoStack>().redo_description();
+    let text = redo.as_ref().map_or("Redo".into(), |d| format!("Redo {d}"));
+    let button = egui::Button::new(text).shortcut_text("Ctrl+Shift+Z");
+    if ui.add_enabled(redo.is_some(), button).clicked() {
+        UndoStack::redo(world);
+        ui.close_menu();
+    }
+}
@@ -0,0 +1,219 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::egui;
+
+const MAX_TOASTS: usize = 5;
+const TOAST_WIDTH: f32 = 280.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl NotificationLevel {
+    pub(crate) fn color(self, visuals: &egui::Visuals) -> egui::Color32 {
+        match self {
+            NotificationLevel::Info => visuals.text_color(),
+            NotificationLevel::Success => egui::Color32::from_rgb(90, 200, 90),
+            NotificationLevel::Warning => visuals.warn_fg_color,
+            NotificationLevel::Error => visuals.error_fg_color,
+        }
+    }
+
+    fn icon(self) -> &'static str {
+        match self {
+            NotificationLevel::Info => "ℹ",
+            NotificationLevel::Success => "✔",
+            NotificationLevel::Warning => "⚠",
+            NotificationLevel::Error => "❌",
+        }
+    }
+}
+
+/// A message shown as a toast and in the status bar.
+///
+/// Can be sent as an event, or pushed directly to the [`Notifications`] resource:
+/// ```rust,ignore
+/// fn system(mut notifications: EventWriter<Notification>) {
+///     notifications.send(Notification::success("Level reloaded"));
+/// }
+/// ```
+#[derive(Event, Clone, Debug)]
+pub struct Notification {
+    pub level: NotificationLevel,
+    pub message: Cow<'static, str>,
+    /// How long the toast stays visible
+    pub duration: Duration,
+}
+
+impl Notification {
+    pub fn new(level: NotificationLevel, message: impl Into<Cow<'static, str>>) -> Self {
+        let duration = match level {
+            NotificationLevel::Info | NotificationLevel::Success => Duration::from_secs(3),
+            NotificationLevel::Warning | NotificationLevel::Error => Duration::from_secs(6),
+        };
+        Notification {
+            level,
+            message: message.into(),
+            duration,
+        }
+    }
+
+    pub fn info(message: impl Into<Cow<'static, str>>) -> Self {
+        Notification::new(NotificationLevel::Info, message)
+    }
+    pub fn success(message: impl Into<Cow<'static, str>>) -> Self {
+        Notification::new(NotificationLevel::Success, message)
+    }
+    pub fn warning(message: impl Into<Cow<'static, str>>) -> Self {
+        Notification::new(NotificationLevel::Warning, message)
+    }
+    pub fn error(message: impl Into<Cow<'static, str>>) -> Self {
+        Notification::new(NotificationLevel::Error, message)
+    }
+
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+}
+
+struct Toast {
+    notification: Notification,
+    /// egui time at which the toast was first drawn
+    shown_at: Option<f64>,
+}
+
+/// Currently visible toasts and the history of recent [`Notification`]s
+#[derive(Resource)]
+pub struct Notifications {
+    toasts: Vec<Toast>,
+    history: VecDeque<Notification>,
+    /// Maximum number of notifications kept in the history
+    pub limit: usize,
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Notifications {
+            toasts: Vec::new(),
+            history: VecDeque::new(),
+            limit: 50,
+        }
+    }
+}
+
+impl Notifications {
+    /// Shows `notification` as a toast and logs it
+    pub fn push(&mut self, notification: Notification) {
+        match notification.level {
+            NotificationLevel::Info | NotificationLevel::Success => {
+                info!("{}", notification.message)
+            }
+            NotificationLevel::Warning => warn!("{}", notification.message),
+            NotificationLevel::Error => error!("{}", notification.message),
+        }
+
+        self.history.push_back(notification.clone());
+        if self.history.len() > self.limit {
+            self.history.pop_front();
+        }
+        self.toasts.push(Toast {
+            notification,
+            shown_at: None,
+        });
+    }
+
+    pub fn info(&mut self, message: impl Into<Cow<'static, str>>) {
+        self.push(Notification::info(message));
+    }
+    pub fn success(&mut self, message: impl Into<Cow<'static, str>>) {
+        self.push(Notification::success(message));
+    }
+    pub fn warning(&mut self, message: impl Into<Cow<'static, str>>) {
+        self.push(Notification::warning(message));
+    }
+    pub fn error(&mut self, message: impl Into<Cow<'static, str>>) {
+        self.push(Notification::error(message));
+    }
+
+    /// The most recent notification, which is shown in the status bar
+    pub fn latest(&self) -> Option<&Notification> {
+        self.history.back()
+    }
+
+    /// Recent notifications, oldest first
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &Notification> {
+        self.history.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.toasts.clear();
+        self.history.clear();
+    }
+}
+
+pub(crate) fn collect_notifications(
+    mut events: EventReader<Notification>,
+    mut notifications: ResMut<Notifications>,
+) {
+    for notification in events.read() {
+        notifications.push(notification.clone());
+    }
+}
+
+/// Draws the toasts stacked in the bottom right corner. Clicking a toast dismisses it.
+pub(crate) fn toasts_ui(ctx: &egui::Context, notifications: &mut Notifications) {
+    let now = ctx.input(|input| input.time);
+    notifications.toasts.retain(|toast| {
+        toast
+            .shown_at
+            .is_none_or(|shown_at| now - shown_at < toast.notification.duration.as_secs_f64())
+    });
+    if notifications.toasts.is_empty() {
+        return;
+    }
+
+    let skip = notifications.toasts.len().saturating_sub(MAX_TOASTS);
+    let mut dismissed = None;
+
+    egui::Area::new(egui::Id::new("editor_toasts"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-8.0, -32.0))
+        .order(egui::Order::Foreground)
+        .interactable(true)
+        .show(ctx, |ui| {
+            ui.set_width(TOAST_WIDTH);
+            for (i, toast) in notifications.toasts.iter_mut().enumerate().skip(skip) {
+                toast.shown_at.get_or_insert(now);
+
+                let level = toast.notification.level;
+                let color = level.color(ui.visuals());
+                let response = egui::Frame::popup(ui.style())
+                    .show(ui, |ui| {
+                        ui.set_width(TOAST_WIDTH);
+                        ui.horizontal_wrapped(|ui| {
+                            ui.colored_label(color, level.icon());
+                            ui.label(toast.notification.message.as_ref());
+                        });
+                    })
+                    .response
+                    .interact(egui::Sense::click());
+                if response.clicked() {
+                    dismissed = Some(i);
+                }
+            }
+        });
+
+    if let Some(i) = dismissed {
+        notifications.toasts.remove(i);
+    }
+
+    // keep repainting so that toasts disappear in reactive mode
+    ctx.request_repaint_after(Duration::from_millis(250));
+}
@@ -0,0 +1,265 @@
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::sync::Arc;
+
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::egui;
+
+use crate::editor::{CommandFn, Editor, EditorEvent, EditorInternalState};
+
+const MAX_RESULTS: usize = 20;
+
+struct PaletteCommand {
+    name: Cow<'static, str>,
+    run: CommandFn,
+}
+
+/// Commands listed in the command palette, next to the editor windows and named entities.
+#[derive(Resource, Default)]
+pub struct PaletteCommands {
+    commands: Vec<PaletteCommand>,
+}
+
+impl PaletteCommands {
+    pub fn add(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        run: impl Fn(&mut World) + Send + Sync + 'static,
+    ) {
+        self.commands.push(PaletteCommand {
+            name: name.into(),
+            run: Arc::new(run),
+        });
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.commands.iter().map(|command| command.name.as_ref())
+    }
+}
+
+/// Extension trait for [`App`] to register a command for the command palette
+pub trait AddPaletteCommand {
+    fn add_palette_command(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        run: impl Fn(&mut World) + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl AddPaletteCommand for App {
+    fn add_palette_command(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        run: impl Fn(&mut World) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(PaletteCommands::default)
+            .add(name, run);
+        self
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct CommandPaletteState {
+    open: bool,
+    query: String,
+    selected: usize,
+}
+
+impl EditorInternalState {
+    pub fn open_command_palette(&mut self) {
+        self.palette.open = true;
+        self.palette.query.clear();
+        self.palette.selected = 0;
+    }
+    pub fn close_command_palette(&mut self) {
+        self.palette.open = false;
+    }
+    pub fn command_palette_open(&self) -> bool {
+        self.palette.open
+    }
+}
+
+#[derive(Clone, Copy)]
+enum PaletteTarget {
+    Window(TypeId),
+    Command(usize),
+    Entity(Entity),
+}
+
+struct PaletteItem {
+    target: PaletteTarget,
+    name: String,
+}
+
+impl PaletteItem {
+    fn kind(&self) -> &'static str {
+        match self.target {
+            PaletteTarget::Window(_) => "window",
+            PaletteTarget::Command(_) => "command",
+            PaletteTarget::Entity(_) => "entity",
+        }
+    }
+}
+
+fn palette_items(world: &mut World, editor: &Editor) -> Vec<PaletteItem> {
+    let windows = editor.window_names().map(|(type_id, name)| PaletteItem {
+        target: PaletteTarget::Window(type_id),
+        name: name.to_owned(),
+    });
+    let commands = world
+        .get_resource::<PaletteCommands>()
+        .into_iter()
+        .flat_map(PaletteCommands::names)
+        .enumerate()
+        .map(|(i, name)| PaletteItem {
+            target: PaletteTarget::Command(i),
+            name: name.to_owned(),
+        });
+    let mut items: Vec<_> = windows.chain(commands).collect();
+
+    let mut named_entities = world.query::<(Entity, &Name)>();
+    items.extend(
+        named_entities
+            .iter(world)
+            .map(|(entity, name)| PaletteItem {
+                target: PaletteTarget::Entity(entity),
+                name: name.as_str().to_owned(),
+            }),
+    );
+
+    items
+}
+
+pub(crate) fn command_palette_ui(
+    world: &mut World,
+    ctx: &egui::Context,
+    editor: &Editor,
+    internal_state: &mut EditorInternalState,
+    editor_events: &mut Events<EditorEvent>,
+) {
+    if !internal_state.palette.open {
+        return;
+    }
+
+    let state = &mut internal_state.palette;
+    let mut matches: Vec<_> = palette_items(world, editor)
+        .into_iter()
+        .filter_map(|item| Some((fuzzy_score(&state.query, &item.name)?, item)))
+        .collect();
+    matches.sort_by_key(|(score, _)| Reverse(*score));
+    matches.truncate(MAX_RESULTS);
+
+    let (up, down, enter, escape) = ctx.input_mut(|input| {
+        (
+            input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+            input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+            input.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+            input.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+        )
+    });
+    if up {
+        state.selected = state.selected.saturating_sub(1);
+    }
+    if down {
+        state.selected += 1;
+    }
+    state.selected = state.selected.min(matches.len().saturating_sub(1));
+
+    let mut chosen = enter.then_some(state.selected);
+    egui::Window::new("Command palette")
+        .title_bar(false)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 60.0))
+        .default_width(400.0)
+        .show(ctx, |ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut state.query)
+                    .hint_text("Search windows, commands and entities")
+                    .desired_width(f32::INFINITY),
+            );
+            response.request_focus();
+            if response.changed() {
+                state.selected = 0;
+            }
+
+            ui.separator();
+            if matches.is_empty() {
+                ui.weak("No matches");
+            }
+            for (i, (_, item)) in matches.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui
+                        .selectable_label(i == state.selected, item.name.as_str())
+                        .clicked()
+                    {
+                        chosen = Some(i);
+                    }
+                    ui.weak(item.kind());
+                });
+            }
+        });
+
+    if escape {
+        state.open = false;
+    }
+
+    let Some((_, item)) = chosen.and_then(|i| matches.get(i)) else {
+        return;
+    };
+    state.open = false;
+    match item.target {
+        PaletteTarget::Window(window) => internal_state.focus_or_open_window(window),
+        PaletteTarget::Command(i) => {
+            let commands = world.resource::<PaletteCommands>();
+            let run = Arc::clone(&commands.commands[i].run);
+            internal_state.pending_commands.push(run);
+        }
+        PaletteTarget::Entity(entity) => {
+            editor_events.send(EditorEvent::SelectEntity(entity));
+        }
+    }
+}
+
+/// Matches the characters of `query` in order, ignoring case and whitespace.
+///
+/// Consecutive matches and matches at the start of a word score higher than scattered ones.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let mut query_chars = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_lowercase())
+        .peekable();
+
+    let mut score = 0;
+    let mut previous: Option<char> = None;
+    let mut previous_matched = false;
+    for c in text.chars() {
+        let Some(&next) = query_chars.peek() else {
+            break;
+        };
+
+        let word_start = previous.is_none_or(|previous| {
+            !previous.is_alphanumeric() || (previous.is_lowercase() && c.is_uppercase())
+        });
+        if c.to_ascii_lowercase() == next {
+            query_chars.next();
+            score += 1;
+            if previous_matched {
+                score += 4;
+            }
+            if word_start {
+                score += 3;
+            }
+            previous_matched = true;
+        } else {
+            score -= 1;
+            previous_matched = false;
+        }
+        previous = Some(c);
+    }
+
+    query_chars.peek().is_none().then_some(score)
+}
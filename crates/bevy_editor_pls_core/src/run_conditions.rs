@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+use crate::Editor;
+
+/// Run condition which is true while the editor is shown, see [`EditorSet::Active`](crate::EditorSet::Active)
+pub fn editor_active() -> impl FnMut(Option<Res<Editor>>) -> bool + Clone {
+    |editor: Option<Res<Editor>>| editor.is_some_and(|editor| editor.active())
+}
+
+/// Run condition which is true while the game is played with the editor hidden, see [`EditorSet::Inactive`](crate::EditorSet::Inactive)
+pub fn editor_inactive() -> impl FnMut(Option<Res<Editor>>) -> bool + Clone {
+    |editor: Option<Res<Editor>>| !editor.is_some_and(|editor| editor.active())
+}
+
+/// Run condition which is true while the editor is shown and the pointer is over a viewport,
+/// or keeps dragging after being pressed in one. Useful for picking, camera controls and other input on the scene.
+pub fn pointer_in_viewport() -> impl FnMut(Option<Res<Editor>>) -> bool + Clone {
+    |editor: Option<Res<Editor>>| {
+        editor.is_some_and(|editor| editor.active() && editor.viewport_interaction_active())
+    }
+}
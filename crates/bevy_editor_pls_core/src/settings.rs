@@ -0,0 +1,83 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::layout::{self, EditorPersistence, LayoutError};
+
+/// Editor-wide settings of the builtin windows and editor cameras.
+///
+/// Saved to [`EditorPersistence::SETTINGS_FILE`] on exit, and can be edited in the `Preferences` window.
+/// Settings inserted by the app replace the saved ones on startup.
+#[derive(Resource, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EditorSettings {
+    /// Render layer of the editor cameras and of the gizmo markers of lights and cameras.
+    /// Should not be used by any game camera.
+    pub render_layer: usize,
+    /// [`Camera::order`] of the editor cameras of the first viewport.
+    /// Every viewport uses three consecutive orders above that.
+    pub camera_order: isize,
+    /// Distance of the camera to the selection when focusing, relative to the size of the selection
+    pub focus_radius_multiplier: f32,
+    /// Scene file used by the `Scenes` window when no filename was entered
+    pub default_scene_filename: String,
+    /// Mouse sensitivity of looking around with the free camera and orbiting with the pan/orbit camera
+    pub look_sensitivity: f32,
+    /// Speed of the free camera in units per second
+    pub fly_speed: f32,
+    /// Speed of the free camera in units per second while the boost key is pressed
+    pub fly_boost_speed: f32,
+    /// Scroll wheel sensitivity of the pan/orbit and 2d pan/zoom cameras
+    pub zoom_sensitivity: f32,
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        EditorSettings {
+            render_layer: Self::DEFAULT_RENDER_LAYER,
+            camera_order: 100,
+            focus_radius_multiplier: 2.0,
+            default_scene_filename: "scene.scn.ron".to_string(),
+            look_sensitivity: 1.0,
+            fly_speed: 5.0,
+            fly_boost_speed: 20.0,
+            zoom_sensitivity: 1.0,
+        }
+    }
+}
+
+impl EditorSettings {
+    /// The default [`render_layer`](EditorSettings::render_layer)
+    pub const DEFAULT_RENDER_LAYER: usize = 19;
+
+    pub fn load(path: &Path) -> Result<Self, LayoutError> {
+        layout::read_ron(path)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), LayoutError> {
+        layout::write_ron(self, path)
+    }
+}
+
+/// Restores the settings saved by a previous run, unless the app inserted its own [`EditorSettings`].
+pub(crate) fn restore_settings(world: &mut World) {
+    if world.contains_resource::<EditorSettings>() {
+        return;
+    }
+    let path = world
+        .get_resource::<EditorPersistence>()
+        .and_then(|persistence| persistence.user_path(EditorPersistence::SETTINGS_FILE))
+        .filter(|path| path.exists());
+
+    let settings = path.map_or_else(EditorSettings::default, |path| {
+        EditorSettings::load(&path).unwrap_or_else(|error| {
+            warn!(
+                "Could not restore editor settings from {}: {error}",
+                path.display()
+            );
+            EditorSettings::default()
+        })
+    });
+    world.insert_resource(settings);
+}
@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::egui;
+
+use crate::notifications::Notifications;
+use crate::Editor;
+
+type StatusBarUiFn = Box<dyn Fn(&Editor, &mut World, &mut egui::Ui) + Send + Sync>;
+
+struct StatusBarItem {
+    order: i32,
+    ui: StatusBarUiFn,
+}
+
+/// Items of the status bar at the bottom of the editor, registered with [`AddStatusBarItem::add_status_bar_item`].
+///
+/// Items are drawn left to right, the latest [`Notification`](crate::notifications::Notification) is shown on the right.
+#[derive(Resource)]
+pub struct StatusBar {
+    items: Vec<StatusBarItem>,
+}
+
+impl Default for StatusBar {
+    fn default() -> Self {
+        let mut status_bar = StatusBar { items: Vec::new() };
+        status_bar.add_item(100, time_scale_ui);
+        status_bar
+    }
+}
+
+impl StatusBar {
+    /// Adds an item to the status bar. Items are sorted by ascending order.
+    ///
+    /// The [`Editor`] is passed in because it is not part of the world while the editor UI is drawn.
+    pub fn add_item(
+        &mut self,
+        order: i32,
+        ui: impl Fn(&Editor, &mut World, &mut egui::Ui) + Send + Sync + 'static,
+    ) {
+        let index = self.items.partition_point(|other| other.order <= order);
+        self.items.insert(
+            index,
+            StatusBarItem {
+                order,
+                ui: Box::new(ui),
+            },
+        );
+    }
+}
+
+pub(crate) fn status_bar_ui(editor: &Editor, world: &mut World, ctx: &egui::Context) {
+    egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            world.resource_scope(|world, status_bar: Mut<StatusBar>| {
+                for (i, item) in status_bar.items.iter().enumerate() {
+                    if i > 0 {
+                        ui.separator();
+                    }
+                    (item.ui)(editor, world, ui);
+                }
+            });
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                latest_notification_ui(world.resource::<Notifications>(), ui);
+            });
+        });
+    });
+}
+
+fn latest_notification_ui(notifications: &Notifications, ui: &mut egui::Ui) {
+    let Some(latest) = notifications.latest() else {
+        return;
+    };
+
+    let color = latest.level.color(ui.visuals());
+    let text = egui::RichText::new(latest.message.as_ref()).color(color);
+    ui.add(egui::Label::new(text).truncate()).on_hover_ui(|ui| {
+        for notification in notifications.history().rev().take(10) {
+            let color = notification.level.color(ui.visuals());
+            ui.colored_label(color, notification.message.as_ref());
+        }
+    });
+}
+
+fn time_scale_ui(_: &Editor, world: &mut World, ui: &mut egui::Ui) {
+    let Some(time) = world.get_resource::<Time<Virtual>>() else {
+        return;
+    };
+    if time.is_paused() {
+        ui.label("⏸ Paused");
+    } else {
+        ui.label(format!("Time ×{:.2}", time.relative_speed()));
+    }
+}
+
+/// Extension trait for [`App`] to add items to the editor status bar
+pub trait AddStatusBarItem {
+    fn add_status_bar_item(
+        &mut self,
+        order: i32,
+        ui: impl Fn(&Editor, &mut World, &mut egui::Ui) + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl AddStatusBarItem for App {
+    fn add_status_bar_item(
+        &mut self,
+        order: i32,
+        ui: impl Fn(&Editor, &mut World, &mut egui::Ui) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(StatusBar::default)
+            .add_item(order, ui);
+        self
+    }
+}
@@ -0,0 +1,263 @@
+use std::any::TypeId;
+
+use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+use bevy_inspector_egui::bevy_egui::{egui, EguiContext, EguiSettings};
+use bevy_inspector_egui::DefaultInspectorConfigPlugin;
+
+use crate::editor::{EditorInternalState, TreeTab};
+use crate::editor_window::EditorWindow;
+use crate::layout::Layout;
+use crate::{AddEditorWindow, Editor, EditorPersistence};
+
+const FRAME_TIME: f32 = 1.0 / 60.0;
+
+/// Headless editor for testing [`EditorWindow`]s without a GPU or an OS window.
+///
+/// Every [`update`](EditorTestApp::update) runs the app's schedules once and then draws the editor ui into an
+/// [`egui::Context`] that is fed with simulated input.
+///
+/// ```rust,ignore
+/// let mut editor = EditorTestApp::new();
+/// editor.add_window::<MyWindow>().show_window::<MyWindow>();
+/// editor.click("Reset");
+/// assert_eq!(editor.window_state::<MyWindow>().unwrap().value, 0);
+/// ```
+pub struct EditorTestApp {
+    pub app: App,
+    ctx: egui::Context,
+    screen_size: egui::Vec2,
+    time: f64,
+    modifiers: egui::Modifiers,
+    pending_events: Vec<egui::Event>,
+    shapes: Vec<egui::epaint::ClippedShape>,
+    finished: bool,
+}
+
+impl Default for EditorTestApp {
+    fn default() -> Self {
+        EditorTestApp::new()
+    }
+}
+
+impl EditorTestApp {
+    /// Builds an [`App`] with [`MinimalPlugins`] and the editor resources.
+    ///
+    /// The editor is always active, and nothing gets persisted to disk.
+    pub fn new() -> Self {
+        let mut app = App::new();
+        // the inspector config expects these to be registered by the plugins of `DefaultPlugins`
+        app.add_plugins(MinimalPlugins)
+            .register_type::<Entity>()
+            .register_type::<Handle<Mesh>>()
+            .register_type::<Handle<Image>>()
+            .register_type::<RenderLayers>()
+            .add_plugins(DefaultInspectorConfigPlugin);
+
+        let mut egui_context = EguiContext::default();
+        let ctx = egui_context.get_mut().clone();
+        let window = app
+            .world_mut()
+            .spawn((Window::default(), egui_context, EguiSettings::default()))
+            .id();
+
+        app.insert_resource(EditorPersistence {
+            dir: None,
+            user_dir: None,
+        });
+        crate::setup_editor(&mut app, Editor::new(window, true));
+
+        EditorTestApp {
+            app,
+            ctx,
+            screen_size: egui::vec2(1280.0, 720.0),
+            time: 0.0,
+            modifiers: egui::Modifiers::NONE,
+            pending_events: Vec::new(),
+            shapes: Vec::new(),
+            finished: false,
+        }
+    }
+
+    pub fn with_screen_size(mut self, width: f32, height: f32) -> Self {
+        self.screen_size = egui::vec2(width, height);
+        self
+    }
+
+    pub fn add_plugins<M>(&mut self, plugins: impl bevy::app::Plugins<M>) -> &mut Self {
+        self.app.add_plugins(plugins);
+        self
+    }
+
+    pub fn add_window<W: EditorWindow>(&mut self) -> &mut Self {
+        self.app.add_editor_window::<W>();
+        self
+    }
+
+    /// Replaces the layout with one where `W` fills the whole screen
+    pub fn show_window<W: EditorWindow>(&mut self) -> &mut Self {
+        let tab = TreeTab::CustomWindow(TypeId::of::<W>(), 0);
+        self.internal_state_mut().layout = Layout {
+            state: egui_dock::DockState::new(vec![tab]),
+            floating_windows: Vec::new(),
+        };
+        self
+    }
+
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    pub fn editor(&self) -> &Editor {
+        self.world().resource::<Editor>()
+    }
+    pub fn internal_state_mut(&mut self) -> Mut<'_, EditorInternalState> {
+        self.world_mut().resource_mut::<EditorInternalState>()
+    }
+
+    pub fn window_state<W: EditorWindow>(&self) -> Option<&W::State> {
+        self.editor().window_state::<W>()
+    }
+    pub fn window_state_mut<W: EditorWindow>(&mut self) -> Option<Mut<'_, W::State>> {
+        let editor = self.world_mut().resource_mut::<Editor>();
+        Mut::filter_map_unchanged(editor, |editor| editor.window_state_mut::<W>())
+    }
+
+    /// Runs the app's schedules and draws one editor frame with the input queued since the last frame.
+    pub fn update(&mut self) -> &mut Self {
+        if !self.finished {
+            self.app.finish();
+            crate::finish_editor(self.app.world_mut());
+            self.app.cleanup();
+            self.finished = true;
+        }
+
+        self.app.update();
+
+        let raw_input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(egui::Pos2::ZERO, self.screen_size)),
+            time: Some(self.time),
+            predicted_dt: FRAME_TIME,
+            modifiers: self.modifiers,
+            events: std::mem::take(&mut self.pending_events),
+            ..default()
+        };
+        self.time += f64::from(FRAME_TIME);
+
+        self.ctx.begin_pass(raw_input);
+        Editor::system(self.app.world_mut());
+        self.shapes = self.ctx.end_pass().shapes;
+
+        self
+    }
+
+    pub fn run_frames(&mut self, frames: usize) -> &mut Self {
+        for _ in 0..frames {
+            self.update();
+        }
+        self
+    }
+
+    /// Screen rect of the visible text `text` drawn in the last frame, e.g. the label of a button.
+    ///
+    /// If the text is drawn multiple times, the topmost occurrence is returned.
+    pub fn find_text(&self, text: &str) -> Option<egui::Rect> {
+        let mut found = None;
+        for clipped in &self.shapes {
+            visit_text_shapes(&clipped.shape, &mut |shape| {
+                if shape.galley.text() != text {
+                    return;
+                }
+                let rect = shape
+                    .galley
+                    .rect
+                    .translate(shape.pos.to_vec2())
+                    .intersect(clipped.clip_rect);
+                if rect.is_positive() {
+                    found = Some(rect);
+                }
+            });
+        }
+        found
+    }
+
+    pub fn contains_text(&self, text: &str) -> bool {
+        self.find_text(text).is_some()
+    }
+
+    /// Clicks on the widget labeled `text` and draws the frames needed for egui to register the click.
+    ///
+    /// Panics if no such text was drawn in the last frame.
+    pub fn click(&mut self, text: &str) -> &mut Self {
+        let rect = self
+            .find_text(text)
+            .unwrap_or_else(|| panic!("no widget labeled `{text}` found in the editor ui"));
+        self.click_at(rect.center())
+    }
+
+    pub fn click_at(&mut self, pos: egui::Pos2) -> &mut Self {
+        self.pending_events.push(egui::Event::PointerMoved(pos));
+        self.update();
+        self.pointer_button(pos, true);
+        self.update();
+        self.pointer_button(pos, false);
+        self.update();
+        // let the ui react to the click, e.g. by opening a menu
+        self.update()
+    }
+
+    /// Types `text` into the currently focused widget
+    pub fn type_text(&mut self, text: &str) -> &mut Self {
+        self.pending_events.push(egui::Event::Text(text.to_owned()));
+        self.update()
+    }
+
+    /// Focuses the widget labeled `label`, e.g. a text edit by its hint text, and types `text` into it
+    pub fn type_into(&mut self, label: &str, text: &str) -> &mut Self {
+        self.click(label).type_text(text)
+    }
+
+    /// Presses and releases `key`, using the modifiers set by [`set_modifiers`](EditorTestApp::set_modifiers)
+    pub fn press_key(&mut self, key: egui::Key) -> &mut Self {
+        for pressed in [true, false] {
+            self.pending_events.push(egui::Event::Key {
+                key,
+                physical_key: Some(key),
+                pressed,
+                repeat: false,
+                modifiers: self.modifiers,
+            });
+            self.update();
+        }
+        self
+    }
+
+    pub fn set_modifiers(&mut self, modifiers: egui::Modifiers) -> &mut Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    fn pointer_button(&mut self, pos: egui::Pos2, pressed: bool) {
+        self.pending_events.push(egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: self.modifiers,
+        });
+    }
+}
+
+fn visit_text_shapes(shape: &egui::Shape, f: &mut impl FnMut(&egui::epaint::TextShape)) {
+    match shape {
+        egui::Shape::Text(text) => f(text),
+        egui::Shape::Vec(shapes) => {
+            for shape in shapes {
+                visit_text_shapes(shape, f);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,189 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::{egui, EguiContext, EguiInput, EguiSettings};
+use serde::{Deserialize, Serialize};
+
+use crate::editor::PopOutWindow;
+use crate::layout::{self, EditorPersistence, LayoutError};
+use crate::Editor;
+
+/// Body text size of the default egui style, which the other text styles are scaled relative to
+const DEFAULT_FONT_SIZE: f32 = 12.5;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum ThemePreset {
+    #[default]
+    Dark,
+    Light,
+    /// Black panels, white text and thicker outlines
+    HighContrast,
+}
+
+impl ThemePreset {
+    pub fn name(self) -> &'static str {
+        match self {
+            ThemePreset::Dark => "Dark",
+            ThemePreset::Light => "Light",
+            ThemePreset::HighContrast => "High contrast",
+        }
+    }
+
+    pub fn all() -> [ThemePreset; 3] {
+        [
+            ThemePreset::Dark,
+            ThemePreset::Light,
+            ThemePreset::HighContrast,
+        ]
+    }
+
+    fn visuals(self) -> egui::Visuals {
+        match self {
+            ThemePreset::Dark => egui::Visuals::dark(),
+            ThemePreset::Light => egui::Visuals::light(),
+            ThemePreset::HighContrast => high_contrast_visuals(),
+        }
+    }
+}
+
+/// Look of the editor ui, applied to the egui style of the editor window and the dock area.
+///
+/// Saved to [`EditorPersistence::THEME_FILE`] on exit, and can be edited in the `Preferences` window.
+/// A theme inserted by the app replaces the saved one on startup.
+#[derive(Resource, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EditorTheme {
+    pub preset: ThemePreset,
+    /// Color of selections, hyperlinks and hovered widget outlines. `None` keeps the color of the preset.
+    pub accent: Option<egui::Color32>,
+    /// Size of body text in points, other text styles are scaled accordingly
+    pub font_size: f32,
+    /// Scale of the whole editor ui, on top of the scale factor of the window
+    pub ui_scale: f32,
+}
+
+impl Default for EditorTheme {
+    fn default() -> Self {
+        EditorTheme {
+            preset: ThemePreset::Dark,
+            accent: None,
+            font_size: DEFAULT_FONT_SIZE,
+            ui_scale: 1.0,
+        }
+    }
+}
+
+impl EditorTheme {
+    pub fn visuals(&self) -> egui::Visuals {
+        let mut visuals = self.preset.visuals();
+        if let Some(accent) = self.accent {
+            visuals.selection.bg_fill = accent;
+            visuals.hyperlink_color = accent;
+            visuals.widgets.hovered.bg_stroke.color = accent;
+        }
+        visuals
+    }
+
+    /// `style` with the visuals and text sizes of this theme
+    pub fn style(&self, style: &egui::Style) -> egui::Style {
+        let mut style = style.clone();
+        style.visuals = self.visuals();
+
+        let font_scale = self.font_size / DEFAULT_FONT_SIZE;
+        style.text_styles = egui::style::default_text_styles();
+        for font_id in style.text_styles.values_mut() {
+            font_id.size *= font_scale;
+        }
+        style
+    }
+
+    pub fn load(path: &Path) -> Result<Self, LayoutError> {
+        layout::read_ron(path)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), LayoutError> {
+        layout::write_ron(self, path)
+    }
+}
+
+fn high_contrast_visuals() -> egui::Visuals {
+    let mut visuals = egui::Visuals::dark();
+    visuals.override_text_color = Some(egui::Color32::WHITE);
+    visuals.panel_fill = egui::Color32::BLACK;
+    visuals.window_fill = egui::Color32::BLACK;
+    visuals.extreme_bg_color = egui::Color32::BLACK;
+    visuals.faint_bg_color = egui::Color32::from_gray(24);
+    visuals.window_stroke = egui::Stroke::new(1.5, egui::Color32::WHITE);
+    visuals.selection.bg_fill = egui::Color32::from_rgb(0, 90, 200);
+    visuals.selection.stroke = egui::Stroke::new(1.5, egui::Color32::WHITE);
+    visuals.hyperlink_color = egui::Color32::from_rgb(255, 220, 0);
+
+    let widgets = &mut visuals.widgets;
+    for (widget, stroke_width) in [
+        (&mut widgets.noninteractive, 1.0),
+        (&mut widgets.inactive, 1.5),
+        (&mut widgets.hovered, 2.0),
+        (&mut widgets.active, 2.0),
+        (&mut widgets.open, 1.5),
+    ] {
+        widget.bg_stroke = egui::Stroke::new(stroke_width, egui::Color32::WHITE);
+        widget.fg_stroke.color = egui::Color32::WHITE;
+    }
+    widgets.inactive.bg_fill = egui::Color32::from_gray(20);
+    widgets.inactive.weak_bg_fill = egui::Color32::from_gray(20);
+    widgets.hovered.bg_stroke.color = egui::Color32::from_rgb(255, 220, 0);
+    visuals
+}
+
+pub(crate) fn apply_theme(
+    theme: Res<EditorTheme>,
+    editor: Res<Editor>,
+    mut windows: Query<(
+        Entity,
+        &mut EguiContext,
+        &mut EguiSettings,
+        Has<PopOutWindow>,
+    )>,
+    // bevy_egui adds its components together, `EguiContext` itself is already borrowed mutably above
+    new_pop_out_windows: Query<(), (With<PopOutWindow>, Added<EguiInput>)>,
+) {
+    if !theme.is_changed() && new_pop_out_windows.is_empty() {
+        return;
+    }
+
+    for (entity, mut egui_context, mut egui_settings, is_pop_out) in &mut windows {
+        if entity != editor.window() && !is_pop_out {
+            continue;
+        }
+
+        let ctx = egui_context.get_mut();
+        let style = theme.style(&ctx.style());
+        ctx.set_style(style);
+
+        if egui_settings.scale_factor != theme.ui_scale {
+            egui_settings.scale_factor = theme.ui_scale;
+        }
+    }
+}
+
+/// Restores the theme saved by a previous run, unless the app inserted its own [`EditorTheme`].
+pub(crate) fn restore_theme(world: &mut World) {
+    if world.contains_resource::<EditorTheme>() {
+        return;
+    }
+    let path = world
+        .get_resource::<EditorPersistence>()
+        .and_then(|persistence| persistence.user_path(EditorPersistence::THEME_FILE))
+        .filter(|path| path.exists());
+
+    let theme = path.map_or_else(EditorTheme::default, |path| {
+        EditorTheme::load(&path).unwrap_or_else(|error| {
+            warn!(
+                "Could not restore editor theme from {}: {error}",
+                path.display()
+            );
+            EditorTheme::default()
+        })
+    });
+    world.insert_resource(theme);
+}
@@ -0,0 +1,761 @@
+use std::any::TypeId;
+use std::borrow::Cow;
+
+use bevy::ecs::entity::EntityHashMap;
+use bevy::prelude::*;
+use bevy::reflect::TypeRegistry;
+
+use crate::EditorEvent;
+
+/// A reversible change made by the editor.
+///
+/// Commands are pushed to the [`UndoStack`] *after* their change was applied to the world.
+/// Entities should be looked up through the [`EntityRemap`], because undoing a despawn respawns
+/// the entities with new ids.
+pub trait EditorCommand: Send + Sync + 'static {
+    /// Short description shown in the `Edit` menu, e.g. `"Edit Transform"`
+    fn description(&self) -> Cow<'static, str>;
+
+    fn undo(&mut self, world: &mut World, entities: &mut EntityRemap);
+    fn redo(&mut self, world: &mut World, entities: &mut EntityRemap);
+}
+
+/// Maps entities referenced by recorded commands to the entities currently in the world.
+#[derive(Default)]
+pub struct EntityRemap(EntityHashMap<Entity>);
+
+impl EntityRemap {
+    pub fn resolve(&self, entity: Entity) -> Entity {
+        let mut resolved = entity;
+        // entities can be respawned multiple times, so follow the chain
+        for _ in 0..self.0.len() {
+            match self.0.get(&resolved) {
+                Some(&next) if next != resolved => resolved = next,
+                _ => break,
+            }
+        }
+        resolved
+    }
+
+    pub fn insert(&mut self, old: Entity, new: Entity) {
+        self.0.insert(old, new);
+    }
+}
+
+/// History of [`EditorCommand`]s which can be undone with `Ctrl+Z` and redone with `Ctrl+Shift+Z`
+#[derive(Resource)]
+pub struct UndoStack {
+    undo: Vec<Box<dyn EditorCommand>>,
+    redo: Vec<Box<dyn EditorCommand>>,
+    entities: EntityRemap,
+    /// Maximum number of commands kept in the history
+    pub limit: usize,
+}
+
+impl Default for UndoStack {
+    fn default() -> Self {
+        UndoStack {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            entities: EntityRemap::default(),
+            limit: 100,
+        }
+    }
+}
+
+impl UndoStack {
+    /// Record a command whose change has already been applied. Clears the redo history.
+    pub fn push(&mut self, command: impl EditorCommand) {
+        self.push_boxed(Box::new(command));
+    }
+
+    pub fn push_boxed(&mut self, command: Box<dyn EditorCommand>) {
+        self.redo.clear();
+        self.undo.push(command);
+        if self.undo.len() > self.limit {
+            let overflow = self.undo.len() - self.limit;
+            self.undo.drain(..overflow);
+        }
+    }
+
+    /// Apply `command` to the world and record it
+    pub fn execute(world: &mut World, mut command: impl EditorCommand) {
+        world.resource_scope(|world, mut stack: Mut<UndoStack>| {
+            command.redo(world, &mut stack.entities);
+            stack.push(command);
+        });
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn undo_description(&self) -> Option<Cow<'static, str>> {
+        self.undo.last().map(|command| command.description())
+    }
+    pub fn redo_description(&self) -> Option<Cow<'static, str>> {
+        self.redo.last().map(|command| command.description())
+    }
+
+    /// Look up where an entity referenced by an earlier command lives now
+    pub fn resolve(&self, entity: Entity) -> Entity {
+        self.entities.resolve(entity)
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Undo the most recent command. Returns `false` if there was nothing to undo.
+    pub fn undo(world: &mut World) -> bool {
+        world.resource_scope(|world, mut stack: Mut<UndoStack>| {
+            let stack = &mut *stack;
+            let Some(mut command) = stack.undo.pop() else {
+                return false;
+            };
+            command.undo(world, &mut stack.entities);
+            stack.redo.push(command);
+            true
+        })
+    }
+
+    /// Redo the most recently undone command. Returns `false` if there was nothing to redo.
+    pub fn redo(world: &mut World) -> bool {
+        world.resource_scope(|world, mut stack: Mut<UndoStack>| {
+            let stack = &mut *stack;
+            let Some(mut command) = stack.redo.pop() else {
+                return false;
+            };
+            command.redo(world, &mut stack.entities);
+            stack.undo.push(command);
+            true
+        })
+    }
+}
+
+/// Several commands which are undone and redone together
+pub struct CommandGroup {
+    pub description: Cow<'static, str>,
+    pub commands: Vec<Box<dyn EditorCommand>>,
+}
+
+impl EditorCommand for CommandGroup {
+    fn description(&self) -> Cow<'static, str> {
+        self.description.clone()
+    }
+
+    fn undo(&mut self, world: &mut World, entities: &mut EntityRemap) {
+        for command in self.commands.iter_mut().rev() {
+            command.undo(world, entities);
+        }
+    }
+
+    fn redo(&mut self, world: &mut World, entities: &mut EntityRemap) {
+        for command in &mut self.commands {
+            command.redo(world, entities);
+        }
+    }
+}
+
+/// Change of a reflected component value. `None` means the component is not present.
+pub struct ReflectComponentChange {
+    pub entity: Entity,
+    pub component: TypeId,
+    pub name: Cow<'static, str>,
+    pub before: Option<Box<dyn PartialReflect>>,
+    pub after: Option<Box<dyn PartialReflect>>,
+}
+
+impl ReflectComponentChange {
+    pub fn new<T: Component + Reflect + TypePath>(
+        entity: Entity,
+        before: Option<T>,
+        after: Option<T>,
+    ) -> Self {
+        ReflectComponentChange {
+            entity,
+            component: TypeId::of::<T>(),
+            name: T::short_type_path().into(),
+            before: before.map(|value| Box::new(value) as Box<dyn PartialReflect>),
+            after: after.map(|value| Box::new(value) as Box<dyn PartialReflect>),
+        }
+    }
+
+    fn set(&self, world: &mut World, entities: &EntityRemap, value: Option<&dyn PartialReflect>) {
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        let Some(reflect_component) = type_registry
+            .get(self.component)
+            .and_then(|registration| registration.data::<ReflectComponent>())
+        else {
+            warn!("cannot undo change of unregistered component {}", self.name);
+            return;
+        };
+
+        let Ok(mut entity) = world.get_entity_mut(entities.resolve(self.entity)) else {
+            return;
+        };
+        match value {
+            Some(value) => reflect_component.apply_or_insert(&mut entity, value, &type_registry),
+            None => reflect_component.remove(&mut entity),
+        }
+    }
+}
+
+impl EditorCommand for ReflectComponentChange {
+    fn description(&self) -> Cow<'static, str> {
+        match (&self.before, &self.after) {
+            (None, Some(_)) => format!("Add {}", self.name).into(),
+            (Some(_), None) => format!("Remove {}", self.name).into(),
+            _ => format!("Edit {}", self.name).into(),
+        }
+    }
+
+    fn undo(&mut self, world: &mut World, entities: &mut EntityRemap) {
+        self.set(world, entities, self.before.as_deref());
+    }
+
+    fn redo(&mut self, world: &mut World, entities: &mut EntityRemap) {
+        self.set(world, entities, self.after.as_deref());
+    }
+}
+
+struct SnapshotRoot {
+    entity: Entity,
+    parent: Option<(Entity, usize)>,
+    /// Only set for non-recursive snapshots, where the children stay alive
+    children: Vec<Entity>,
+}
+
+/// Reflected components of entities which are about to be despawned.
+///
+/// Components which aren't registered in the type registry are lost.
+struct EntitySnapshot {
+    scene: DynamicScene,
+    roots: Vec<SnapshotRoot>,
+    recursive: bool,
+}
+
+impl EntitySnapshot {
+    fn take(world: &World, roots: &[Entity], recursive: bool) -> Self {
+        let mut all_entities = Vec::new();
+        let roots: Vec<_> = roots
+            .iter()
+            .filter(|&&entity| world.get_entity(entity).is_ok())
+            .map(|&entity| {
+                let parent = world.get::<Parent>(entity).map(|parent| {
+                    let index = world
+                        .get::<Children>(parent.get())
+                        .and_then(|children| children.iter().position(|&child| child == entity))
+                        .unwrap_or(0);
+                    (parent.get(), index)
+                });
+                let children = world
+                    .get::<Children>(entity)
+                    .map(|children| children.to_vec())
+                    .unwrap_or_default();
+
+                if recursive {
+                    collect_descendants(world, entity, &mut all_entities);
+                    SnapshotRoot {
+                        entity,
+                        parent,
+                        children: Vec::new(),
+                    }
+                } else {
+                    all_entities.push(entity);
+                    SnapshotRoot {
+                        entity,
+                        parent,
+                        children,
+                    }
+                }
+            })
+            .collect();
+
+        let mut scene = DynamicSceneBuilder::from_world(world)
+            .extract_entities(all_entities.into_iter())
+            .build();
+
+        // references leaving the snapshot are restored manually
+        for dynamic_entity in &mut scene.entities {
+            if !roots
+                .iter()
+                .any(|root| root.entity == dynamic_entity.entity)
+            {
+                continue;
+            }
+            dynamic_entity.components.retain(|component| {
+                let type_id = component
+                    .get_represented_type_info()
+                    .map(|info| info.type_id());
+                type_id != Some(TypeId::of::<Parent>())
+                    && (recursive || type_id != Some(TypeId::of::<Children>()))
+            });
+        }
+
+        EntitySnapshot {
+            scene,
+            roots,
+            recursive,
+        }
+    }
+
+    fn despawn(&self, world: &mut World) {
+        for root in &self.roots {
+            if self.recursive {
+                bevy::hierarchy::despawn_with_children_recursive(world, root.entity, true);
+            } else if let Ok(mut entity) = world.get_entity_mut(root.entity) {
+                entity.remove_parent();
+                entity.clear_children();
+                entity.despawn();
+            }
+        }
+    }
+
+    fn restore(&self, world: &mut World, entities: &mut EntityRemap) {
+        let mut entity_map = EntityHashMap::default();
+        if let Err(error) = self.scene.write_to_world(world, &mut entity_map) {
+            error!("failed to restore despawned entities: {error}");
+            return;
+        }
+        for (&old, &new) in &entity_map {
+            entities.insert(old, new);
+        }
+
+        for root in &self.roots {
+            let Some(&new_root) = entity_map.get(&root.entity) else {
+                continue;
+            };
+            if let Some((parent, index)) = root.parent {
+                let parent = entities.resolve(parent);
+                if let Ok(mut parent) = world.get_entity_mut(parent) {
+                    parent.insert_children(index, &[new_root]);
+                }
+            }
+            if !root.children.is_empty() {
+                let children: Vec<_> = root
+                    .children
+                    .iter()
+                    .map(|&child| entities.resolve(child))
+                    .filter(|&child| world.get_entity(child).is_ok())
+                    .collect();
+                world.entity_mut(new_root).add_children(&children);
+            }
+        }
+    }
+}
+
+fn collect_descendants(world: &World, entity: Entity, out: &mut Vec<Entity>) {
+    out.push(entity);
+    if let Some(children) = world.get::<Children>(entity) {
+        for &child in children {
+            collect_descendants(world, child, out);
+        }
+    }
+}
+
+/// Despawning entities, which restores them from a snapshot when undone
+pub struct DespawnEntities {
+    entities: Vec<Entity>,
+    recursive: bool,
+    snapshot: Option<EntitySnapshot>,
+}
+
+impl DespawnEntities {
+    /// Despawn `entities` (and their descendants if `recursive`) and return the command to record.
+    pub fn execute(world: &mut World, entities: &[Entity], recursive: bool) -> Self {
+        let mut command = DespawnEntities {
+            entities: entities.to_vec(),
+            recursive,
+            snapshot: None,
+        };
+        command.redo(world, &mut EntityRemap::default());
+
+        if let Some(mut editor_events) = world.get_resource_mut::<Events<EditorEvent>>() {
+            for &entity in entities {
+                editor_events.send(EditorEvent::EntityDespawned { entity, recursive });
+            }
+        }
+        command
+    }
+}
+
+impl EditorCommand for DespawnEntities {
+    fn description(&self) -> Cow<'static, str> {
+        match self.entities.len() {
+            1 => "Despawn entity".into(),
+            n => format!("Despawn {n} entities").into(),
+        }
+    }
+
+    fn undo(&mut self, world: &mut World, entities: &mut EntityRemap) {
+        if let Some(snapshot) = self.snapshot.take() {
+            snapshot.restore(world, entities);
+        }
+    }
+
+    fn redo(&mut self, world: &mut World, entities: &mut EntityRemap) {
+        let current: Vec<_> = self.entities.iter().map(|&e| entities.resolve(e)).collect();
+        let snapshot = EntitySnapshot::take(world, &current, self.recursive);
+        snapshot.despawn(world);
+        self.snapshot = Some(snapshot);
+    }
+}
+
+/// Spawning entities, which despawns them again when undone
+pub struct SpawnEntities {
+    entities: Vec<Entity>,
+    snapshot: Option<EntitySnapshot>,
+}
+
+impl SpawnEntities {
+    /// Record that `entities` were spawned. Their descendants are included as well.
+    pub fn new(entities: Vec<Entity>) -> Self {
+        SpawnEntities {
+            entities,
+            snapshot: None,
+        }
+    }
+}
+
+impl EditorCommand for SpawnEntities {
+    fn description(&self) -> Cow<'static, str> {
+        match self.entities.len() {
+            1 => "Spawn entity".into(),
+            n => format!("Spawn {n} entities").into(),
+        }
+    }
+
+    fn undo(&mut self, world: &mut World, entities: &mut EntityRemap) {
+        let current: Vec<_> = self.entities.iter().map(|&e| entities.resolve(e)).collect();
+        let snapshot = EntitySnapshot::take(world, &current, true);
+        snapshot.despawn(world);
+        self.snapshot = Some(snapshot);
+    }
+
+    fn redo(&mut self, world: &mut World, entities: &mut EntityRemap) {
+        if let Some(snapshot) = self.snapshot.take() {
+            snapshot.restore(world, entities);
+        }
+    }
+}
+
+/// Run `f` and record the component changes it makes to `entities` as a single undo step.
+pub fn track_edits<R>(
+    world: &mut World,
+    entities: &[Entity],
+    f: impl FnOnce(&mut World) -> R,
+) -> R {
+    let mut tracker = ComponentEditTracker::default();
+    let result = tracker.track(world, entities, f);
+    tracker.finish(&mut world.resource_mut::<UndoStack>());
+    result
+}
+
+/// Records component edits made by UI code, like the inspector, as [`ReflectComponentChange`]s.
+///
+/// Call [`ComponentEditTracker::track`] around the UI every frame, and [`ComponentEditTracker::finish`]
+/// once the user stopped interacting, so that e.g. a whole drag ends up as a single undo step.
+#[derive(Default)]
+pub struct ComponentEditTracker {
+    pending: Vec<ReflectComponentChange>,
+}
+
+type ComponentSnapshot = Vec<(TypeId, Box<dyn PartialReflect>)>;
+
+fn snapshot_components(
+    world: &World,
+    entity: Entity,
+    type_registry: &TypeRegistry,
+) -> ComponentSnapshot {
+    let Ok(entity_ref) = world.get_entity(entity) else {
+        return Vec::new();
+    };
+    entity_ref
+        .archetype()
+        .components()
+        .filter_map(|component_id| {
+            let type_id = world.components().get_info(component_id)?.type_id()?;
+            let reflect_component = type_registry.get_type_data::<ReflectComponent>(type_id)?;
+            let value = reflect_component.reflect(entity_ref)?;
+            Some((type_id, value.clone_value()))
+        })
+        .collect()
+}
+
+impl ComponentEditTracker {
+    /// Run `f`, and record every reflected component of `entities` which it added, removed or changed.
+    pub fn track<R>(
+        &mut self,
+        world: &mut World,
+        entities: &[Entity],
+        f: impl FnOnce(&mut World) -> R,
+    ) -> R {
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+
+        let before: Vec<_> = entities
+            .iter()
+            .map(|&entity| snapshot_components(world, entity, &type_registry))
+            .collect();
+        let result = f(world);
+
+        for (&entity, before) in entities.iter().zip(before) {
+            let mut after = snapshot_components(world, entity, &type_registry);
+            for (type_id, before_value) in before {
+                let after_value = after
+                    .iter()
+                    .position(|(after_type, _)| *after_type == type_id)
+                    .map(|i| after.swap_remove(i).1);
+                let changed = match &after_value {
+                    Some(after_value) => {
+                        before_value.reflect_partial_eq(after_value.as_ref()) == Some(false)
+                    }
+                    None => true,
+                };
+                if changed {
+                    self.record(
+                        entity,
+                        type_id,
+                        Some(before_value),
+                        after_value,
+                        &type_registry,
+                    );
+                }
+            }
+            for (type_id, added) in after {
+                self.record(entity, type_id, None, Some(added), &type_registry);
+            }
+        }
+
+        result
+    }
+
+    fn record(
+        &mut self,
+        entity: Entity,
+        component: TypeId,
+        before: Option<Box<dyn PartialReflect>>,
+        after: Option<Box<dyn PartialReflect>>,
+        type_registry: &TypeRegistry,
+    ) {
+        // keep the value from before the first change of an ongoing edit
+        if let Some(pending) = self
+            .pending
+            .iter_mut()
+            .find(|change| change.entity == entity && change.component == component)
+        {
+            pending.after = after;
+            return;
+        }
+
+        let name = type_registry
+            .get(component)
+            .map(|registration| registration.type_info().type_path_table().short_path())
+            .unwrap_or("component");
+        self.pending.push(ReflectComponentChange {
+            entity,
+            component,
+            name: name.to_owned().into(),
+            before,
+            after,
+        });
+    }
+
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Push all changes recorded since the last call as one undo step.
+    pub fn finish(&mut self, undo_stack: &mut UndoStack) {
+        let mut pending = std::mem::take(&mut self.pending);
+        match pending.len() {
+            0 => {}
+            1 => undo_stack.push(pending.remove(0)),
+            _ => {
+                let description = match pending.iter().all(|c| c.name == pending[0].name) {
+                    true => pending[0].description(),
+                    false => format!("Edit {} components", pending.len()).into(),
+                };
+                undo_stack.push(CommandGroup {
+                    description,
+                    commands: pending
+                        .into_iter()
+                        .map(|change| Box::new(change) as Box<dyn EditorCommand>)
+                        .collect(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    struct Increment;
+
+    impl EditorCommand for Increment {
+        fn description(&self) -> Cow<'static, str> {
+            "Increment".into()
+        }
+        fn undo(&mut self, world: &mut World, _: &mut EntityRemap) {
+            world.resource_mut::<Counter>().0 -= 1;
+        }
+        fn redo(&mut self, world: &mut World, _: &mut EntityRemap) {
+            world.resource_mut::<Counter>().0 += 1;
+        }
+    }
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.init_resource::<UndoStack>();
+        world.init_resource::<Counter>();
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut type_registry = type_registry.write();
+            type_registry.register::<Name>();
+            type_registry.register::<Transform>();
+            type_registry.register::<Parent>();
+            type_registry.register::<Children>();
+        }
+        world.insert_resource(type_registry);
+        world
+    }
+
+    #[test]
+    fn pushing_clears_the_redo_history() {
+        let mut world = world();
+        UndoStack::execute(&mut world, Increment);
+        UndoStack::execute(&mut world, Increment);
+        assert!(UndoStack::undo(&mut world));
+        assert!(world.resource::<UndoStack>().can_redo());
+
+        UndoStack::execute(&mut world, Increment);
+        assert!(!world.resource::<UndoStack>().can_redo());
+        assert_eq!(world.resource::<Counter>().0, 2);
+
+        assert!(UndoStack::undo(&mut world));
+        assert!(UndoStack::undo(&mut world));
+        assert!(!UndoStack::undo(&mut world));
+        assert_eq!(world.resource::<Counter>().0, 0);
+    }
+
+    #[test]
+    fn history_is_limited() {
+        let mut world = world();
+        world.resource_mut::<UndoStack>().limit = 2;
+        for _ in 0..3 {
+            UndoStack::execute(&mut world, Increment);
+        }
+
+        while UndoStack::undo(&mut world) {}
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+
+    #[test]
+    fn tracked_edits_are_merged_into_one_step() {
+        let mut world = world();
+        let entity = world.spawn(Transform::from_xyz(1.0, 0.0, 0.0)).id();
+
+        let mut tracker = ComponentEditTracker::default();
+        for x in [2.0, 3.0] {
+            tracker.track(&mut world, &[entity], |world| {
+                world.get_mut::<Transform>(entity).unwrap().translation.x = x;
+            });
+        }
+        tracker.finish(&mut world.resource_mut::<UndoStack>());
+        assert_eq!(
+            world.resource::<UndoStack>().undo_description().as_deref(),
+            Some("Edit Transform")
+        );
+
+        UndoStack::undo(&mut world);
+        assert_eq!(world.get::<Transform>(entity).unwrap().translation.x, 1.0);
+        assert!(!world.resource::<UndoStack>().can_undo());
+
+        UndoStack::redo(&mut world);
+        assert_eq!(world.get::<Transform>(entity).unwrap().translation.x, 3.0);
+    }
+
+    #[test]
+    fn undoing_a_recursive_despawn_restores_the_hierarchy() {
+        let mut world = world();
+        let parent = world.spawn(Name::new("parent")).id();
+        let child = world
+            .spawn((Name::new("child"), Transform::from_xyz(0.0, 1.0, 0.0)))
+            .set_parent(parent)
+            .id();
+
+        let command = DespawnEntities::execute(&mut world, &[parent], true);
+        world.resource_mut::<UndoStack>().push(command);
+        assert!(world.get_entity(parent).is_err());
+        assert!(world.get_entity(child).is_err());
+
+        UndoStack::undo(&mut world);
+        let stack = world.resource::<UndoStack>();
+        let (new_parent, new_child) = (stack.resolve(parent), stack.resolve(child));
+        assert_eq!(
+            world.get::<Name>(new_parent).map(Name::as_str),
+            Some("parent")
+        );
+        assert_eq!(
+            world.get::<Parent>(new_child).map(Parent::get),
+            Some(new_parent)
+        );
+        assert_eq!(
+            world.get::<Transform>(new_child).unwrap().translation.y,
+            1.0
+        );
+
+        UndoStack::redo(&mut world);
+        assert!(world.get_entity(new_parent).is_err());
+        assert!(world.get_entity(new_child).is_err());
+    }
+
+    #[test]
+    fn undoing_a_despawn_reattaches_the_kept_children() {
+        let mut world = world();
+        let parent = world.spawn(Name::new("parent")).id();
+        let child = world.spawn(Name::new("child")).set_parent(parent).id();
+
+        let command = DespawnEntities::execute(&mut world, &[parent], false);
+        world.resource_mut::<UndoStack>().push(command);
+        assert!(world.get_entity(parent).is_err());
+        assert!(world.get::<Parent>(child).is_none());
+
+        UndoStack::undo(&mut world);
+        let new_parent = world.resource::<UndoStack>().resolve(parent);
+        assert_eq!(
+            world.get::<Parent>(child).map(Parent::get),
+            Some(new_parent)
+        );
+    }
+
+    #[test]
+    fn undoing_a_spawn_despawns_and_redo_restores() {
+        let mut world = world();
+        let entity = world.spawn(Name::new("spawned")).id();
+        world
+            .resource_mut::<UndoStack>()
+            .push(SpawnEntities::new(vec![entity]));
+
+        UndoStack::undo(&mut world);
+        assert!(world.get_entity(entity).is_err());
+
+        UndoStack::redo(&mut world);
+        let respawned = world.resource::<UndoStack>().resolve(entity);
+        assert_eq!(
+            world.get::<Name>(respawned).map(Name::as_str),
+            Some("spawned")
+        );
+    }
+}
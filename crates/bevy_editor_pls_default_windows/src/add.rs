@@ -7,6 +7,7 @@ use bevy::{
     render::view::RenderLayers,
 };
 use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
+use bevy_editor_pls_core::undo::{SpawnEntities, UndoStack};
 use bevy_inspector_egui::egui;
 use indexmap::IndexMap;
 
@@ -91,14 +92,17 @@ fn add_ui_button(world: &mut World, ui: &mut egui::Ui, mut cx: EditorWindowConte
         add_ui(ui, state).map(|add_item| {
             let entity = world.spawn_empty().id();
             add_item.add_to_entity(world, entity);
+            world
+                .resource_mut::<UndoStack>()
+                .push(SpawnEntities::new(vec![entity]));
             entity
         })
     });
 
-    if let Some(Some(entity)) = response.inner {
-        if let Some(hierarchy_state) = cx.state_mut::<HierarchyWindow>() {
-            hierarchy_state.selected.select_replace(entity);
-        }
+    if let Some(Some(entity)) = response.inner
+        && let Some(hierarchy_state) = cx.state_mut::<HierarchyWindow>()
+    {
+        hierarchy_state.selected.select_replace(entity);
     }
 }
 
@@ -15,7 +15,11 @@ impl EditorWindow for AssetsWindow {
     const NAME: &'static str = "Assets";
 
     fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
-        let selection = &mut cx.state_mut::<InspectorWindow>().unwrap().selected;
+        let Some(inspector_state) = cx.state_mut::<InspectorWindow>() else {
+            ui.label("Selecting assets requires the Inspector window");
+            return;
+        };
+        let selection = &mut inspector_state.selected;
         let type_registry = world.resource::<AppTypeRegistry>();
         let type_registry = type_registry.read();
 
@@ -40,7 +44,7 @@ fn select_asset(
             ))
         })
         .collect();
-    assets.sort_by(|(name_a, ..), (name_b, ..)| name_a.cmp(name_b));
+    assets.sort_by_key(|(name, ..)| *name);
 
     for (asset_name, asset_type_id, reflect_asset) in assets {
         let handles: Vec<_> = reflect_asset.ids(world).collect();
@@ -4,7 +4,10 @@ use bevy::{
     input::mouse::{MouseScrollUnit, MouseWheel},
     prelude::*,
 };
-use bevy_editor_pls_core::Editor;
+use bevy_editor_pls_core::{run_conditions::pointer_in_viewport, Editor, EditorSettings};
+use bevy_inspector_egui::{bevy_egui::EguiSettings, egui};
+
+use super::{egui_to_viewport_position, EditorViewport};
 
 #[derive(SystemSet, PartialEq, Eq, Clone, Hash, Debug)]
 pub(crate) enum CameraSystem {
@@ -18,9 +21,10 @@ impl Plugin for PanCamPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            camera_movement.in_set(CameraSystem::EditorCam2dPanZoom),
-        )
-        .add_systems(Update, camera_zoom.in_set(CameraSystem::EditorCam2dPanZoom));
+            (camera_movement, camera_zoom)
+                .run_if(pointer_in_viewport())
+                .in_set(CameraSystem::EditorCam2dPanZoom),
+        );
     }
 }
 
@@ -28,6 +32,7 @@ impl Plugin for PanCamPlugin {
 fn camera_zoom(
     mut query: Query<(&PanCamControls, &mut OrthographicProjection)>,
     mut scroll_events: EventReader<MouseWheel>,
+    settings: Res<EditorSettings>,
 ) {
     let pixels_per_line = 100.; // Maybe make configurable?
     let scroll = scroll_events
@@ -42,53 +47,67 @@ fn camera_zoom(
         return;
     }
 
-    for (_cam, mut projection) in query.iter_mut() {
-        projection.scale = (projection.scale * (1. + -scroll * 0.001)).max(0.00001);
+    let zoom = scroll * 0.001 * settings.zoom_sensitivity;
+    for (cam, mut projection) in query.iter_mut() {
+        if !cam.enabled {
+            continue;
+        }
+        projection.scale = (projection.scale * (1. + -zoom)).max(0.00001);
     }
 }
 
 fn camera_movement(
     editor: Res<Editor>,
-    window: Query<&Window>,
+    window: Query<(&Window, &EguiSettings)>,
     mouse_buttons: Res<ButtonInput<MouseButton>>,
-    mut query: Query<(&PanCamControls, &mut Transform, &OrthographicProjection)>,
+    mut query: Query<(
+        &PanCamControls,
+        &Camera,
+        &EditorViewport,
+        &mut Transform,
+        &OrthographicProjection,
+    )>,
     mut last_pos: Local<Option<Vec2>>,
 ) {
-    let Ok(window) = window.get(editor.window()) else {
+    let Ok((window, egui_settings)) = window.get(editor.window()) else {
         return;
     };
 
     // Use position instead of MouseMotion, otherwise we don't get acceleration movement
-    let current_pos = match window.cursor_position() {
-        Some(current_pos) => current_pos,
-        None => return,
+    let Some(cursor_pos) = window.cursor_position() else {
+        return;
     };
-    let delta = current_pos - last_pos.unwrap_or(current_pos);
+    let pointer = cursor_pos / egui_settings.scale_factor;
+    let pointer = egui::pos2(pointer.x, pointer.y);
 
-    let delta = Vec2::new(delta.x, -delta.y);
-
-    for (cam, mut transform, projection) in query.iter_mut() {
+    for (cam, camera, &EditorViewport(viewport), mut transform, projection) in query.iter_mut() {
         if !cam.enabled {
             continue;
         }
 
+        // the camera viewport is the offscreen image in `ViewportRenderMode::Image`, not the window
+        let Some(current_pos) = egui_to_viewport_position(&editor, camera, viewport, pointer)
+        else {
+            *last_pos = None;
+            continue;
+        };
+        let delta = current_pos - last_pos.unwrap_or(current_pos);
+        let delta = Vec2::new(delta.x, -delta.y);
+
         if cam
             .grab_buttons
             .iter()
             .any(|btn| mouse_buttons.pressed(*btn))
+            && let Some(viewport_size) = camera.logical_viewport_size()
         {
-            let scaling = Vec2::new(
-                window.width() / projection.area.width(),
-                window.height() / projection.area.height(),
-            ) * projection.scale;
-
+            let scaling = projection.area.size() / viewport_size;
             transform.translation -= (delta * scaling).extend(0.);
         }
+        *last_pos = Some(current_pos);
     }
-    *last_pos = Some(current_pos);
 }
 
-#[derive(Component)]
+#[derive(Component, Clone)]
 pub struct PanCamControls {
     pub enabled: bool,
     pub grab_buttons: Vec<MouseButton>,
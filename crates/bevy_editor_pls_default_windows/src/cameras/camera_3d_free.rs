@@ -1,13 +1,17 @@
 use bevy::{input::mouse::MouseMotion, prelude::*};
+use bevy_editor_pls_core::{run_conditions::pointer_in_viewport, EditorSet, EditorSettings};
 
 pub(crate) struct FlycamPlugin;
 impl Plugin for FlycamPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            camera_movement.in_set(CameraSystem::EditorCam3dFree),
-        )
-        .add_systems(Update, camera_look);
+            (
+                camera_movement.in_set(EditorSet::Active),
+                camera_look.run_if(pointer_in_viewport()),
+            )
+                .in_set(CameraSystem::EditorCam3dFree),
+        );
     }
 }
 
@@ -16,7 +20,7 @@ pub(crate) enum CameraSystem {
     EditorCam3dFree,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone)]
 pub struct FlycamControls {
     pub yaw: f32,
     pub pitch: f32,
@@ -55,11 +59,14 @@ fn camera_movement(
     mut cam: Query<(&FlycamControls, &mut Transform)>,
     time: Res<Time>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    settings: Res<EditorSettings>,
 ) {
-    let (flycam, mut cam_transform) = cam.single_mut();
-    if !flycam.enable_movement {
+    // only the camera of the active viewport can move
+    let Some((flycam, mut cam_transform)) =
+        cam.iter_mut().find(|(flycam, _)| flycam.enable_movement)
+    else {
         return;
-    }
+    };
 
     let if_then_1 = |b| if b { 1.0 } else { 0.0 };
     let forward = if_then_1(keyboard_input.pressed(flycam.key_forward))
@@ -74,9 +81,9 @@ fn camera_movement(
     }
 
     let speed = if keyboard_input.pressed(flycam.key_boost) {
-        20.0
+        settings.fly_boost_speed
     } else {
-        5.0
+        settings.fly_speed
     };
 
     let movement = Vec3::new(sideways, forward, up).normalize_or_zero() * speed * time.delta_secs();
@@ -91,13 +98,17 @@ fn camera_look(
     mouse_input: Res<ButtonInput<MouseButton>>,
     mut mouse_motion_event_reader: EventReader<MouseMotion>,
     mut query: Query<(&mut FlycamControls, &mut Transform)>,
+    settings: Res<EditorSettings>,
 ) {
-    let (mut flycam, mut transform) = query.single_mut();
-    if !flycam.enable_look || !mouse_input.pressed(MouseButton::Right) {
+    let Some((mut flycam, mut transform)) = query
+        .iter_mut()
+        .find(|(flycam, _)| flycam.enable_look)
+        .filter(|_| mouse_input.pressed(MouseButton::Right))
+    else {
         //Prevent accumulation of irrelevant events
         mouse_motion_event_reader.clear();
         return;
-    }
+    };
     let mut delta: Vec2 = Vec2::ZERO;
     for event in mouse_motion_event_reader.read() {
         delta += event.delta;
@@ -106,8 +117,9 @@ fn camera_look(
         return;
     }
 
-    flycam.yaw -= delta.x / 180.0 * flycam.sensitivity;
-    flycam.pitch -= delta.y / 180.0 * flycam.sensitivity;
+    let sensitivity = flycam.sensitivity * settings.look_sensitivity;
+    flycam.yaw -= delta.x / 180.0 * sensitivity;
+    flycam.pitch -= delta.y / 180.0 * sensitivity;
 
     flycam.pitch = flycam
         .pitch
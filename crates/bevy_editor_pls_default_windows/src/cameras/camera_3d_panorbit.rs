@@ -4,20 +4,22 @@ use bevy::{
     input::mouse::{MouseMotion, MouseWheel},
     prelude::*,
 };
-use bevy_editor_pls_core::Editor;
+use bevy_editor_pls_core::{run_conditions::pointer_in_viewport, Editor, EditorSettings};
 
 pub struct PanOrbitCameraPlugin;
 impl Plugin for PanOrbitCameraPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            pan_orbit_camera.in_set(CameraSystem::EditorCam3dPanOrbit),
+            pan_orbit_camera
+                .run_if(pointer_in_viewport())
+                .in_set(CameraSystem::EditorCam3dPanOrbit),
         );
     }
 }
 
 /// Tags an entity as capable of panning and orbiting.
-#[derive(Component)]
+#[derive(Component, Clone)]
 pub struct PanOrbitCamera {
     pub enabled: bool,
 
@@ -53,6 +55,7 @@ pub(crate) enum CameraSystem {
 /// Pan the camera with middle mouse click, zoom with scroll wheel, orbit with right mouse click.
 fn pan_orbit_camera(
     editor: Res<Editor>,
+    settings: Res<EditorSettings>,
     window: Query<&Window>,
     mut ev_motion: EventReader<MouseMotion>,
     mut ev_scroll: EventReader<MouseWheel>,
@@ -67,14 +70,14 @@ fn pan_orbit_camera(
     };
 
     // change input mapping for orbit and panning here
-    let (mut pan_orbit, mut transform, projection) = query.single_mut();
-
-    if !pan_orbit.enabled {
+    let Some((mut pan_orbit, mut transform, projection)) =
+        query.iter_mut().find(|(pan_orbit, ..)| pan_orbit.enabled)
+    else {
         //Prevent accumulation of irrelevant events
         ev_motion.clear();
         ev_scroll.clear();
         return;
-    }
+    };
 
     let mut pan = Vec2::ZERO;
     let mut rotation_move = Vec2::ZERO;
@@ -114,6 +117,7 @@ fn pan_orbit_camera(
     let mut any = false;
     if rotation_move.length_squared() > 0.0 {
         any = true;
+        let rotation_move = rotation_move * settings.look_sensitivity;
         let delta_x = {
             let delta = rotation_move.x / 180.0;
             if pan_orbit.upside_down {
@@ -143,7 +147,7 @@ fn pan_orbit_camera(
         pan_orbit.focus += translation;
     } else if scroll.abs() > 0.0 {
         any = true;
-        pan_orbit.radius -= scroll * pan_orbit.radius * 0.1;
+        pan_orbit.radius -= scroll * pan_orbit.radius * 0.1 * settings.zoom_sensitivity;
         // dont allow zoom to reach zero or you get stuck
         pan_orbit.radius = f32::max(pan_orbit.radius, 0.05);
     }
@@ -1,35 +1,69 @@
 pub mod camera_2d_panzoom;
 pub mod camera_3d_free;
 pub mod camera_3d_panorbit;
+use std::collections::BTreeMap;
+
 use crate::scenes::NotInScene;
 
 use bevy::render::camera::RenderTarget;
+use bevy::render::render_resource::{
+    Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+};
 use bevy::render::view::RenderLayers;
-use bevy::utils::HashSet;
+use bevy::utils::{HashMap, HashSet};
 use bevy::window::WindowRef;
 use bevy::{prelude::*, render::primitives::Aabb};
 use bevy_editor_pls_core::{
-    editor_window::{EditorWindow, EditorWindowContext},
-    Editor, EditorEvent,
+    editor::EditorInternalState,
+    editor_window::{load_ron, save_ron, EditorWindow, EditorWindowContext},
+    AddStatusBarItem, Editor, EditorEvent, EditorSettings, Notifications,
 };
+use bevy_inspector_egui::bevy_egui::{EguiSettings, EguiUserTextures};
 use bevy_inspector_egui::egui;
-use transform_gizmo_bevy::GizmoCamera;
+use serde::{Deserialize, Serialize};
+use transform_gizmo_bevy::{GizmoCamera, GizmoOptions};
 // use bevy_mod_picking::prelude::PickRaycastSource;
 
 use crate::hierarchy::{HideInEditor, HierarchyWindow};
 
 use self::camera_3d_panorbit::PanOrbitCamera;
 
-pub const EDITOR_RENDER_LAYER: usize = 19;
+/// Render layer of the editor cameras
+#[deprecated(note = "use `EditorSettings::render_layer`, which can be changed at runtime")]
+pub const EDITOR_RENDER_LAYER: usize = EditorSettings::DEFAULT_RENDER_LAYER;
 
 // Present on all editor cameras
 #[derive(Component)]
 pub struct EditorCamera;
 
-// Present only one the one currently active camera
+// Present only on the current camera of the active viewport
 #[derive(Component)]
 pub struct ActiveEditorCamera;
 
+/// The viewport an editor camera renders to, see [`Editor::viewports`].
+///
+/// Every viewport has its own set of editor cameras.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct EditorViewport(pub u32);
+
+/// How the editor cameras get their output into the viewports.
+///
+/// Can be inserted as a resource before adding the editor, or changed in the `Cameras` window.
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ViewportRenderMode {
+    /// Render directly into the window, restricting the camera viewports to the viewport tabs
+    #[default]
+    Window,
+    /// Render into an offscreen [`Image`] per viewport, which is drawn as a texture inside of the viewport tab.
+    ///
+    /// Unlike [`ViewportRenderMode::Window`], egui windows floating above a viewport correctly cover it.
+    Image,
+}
+
+/// Offscreen render targets of the viewports in [`ViewportRenderMode::Image`]
+#[derive(Resource, Default)]
+struct ViewportImages(HashMap<u32, Handle<Image>>);
+
 // Marker component for the 3d free camera
 #[derive(Component)]
 struct EditorCamera3dFree;
@@ -44,7 +78,7 @@ struct EditorCamera2dPanZoom;
 
 pub struct CameraWindow;
 
-#[derive(Clone, Copy, PartialEq, Default)]
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 pub enum EditorCamKind {
     D2PanZoom,
     D3Free,
@@ -70,16 +104,31 @@ impl EditorCamKind {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CameraWindowState {
-    // make sure to keep the `ActiveEditorCamera` marker component in sync with this field
-    editor_cam: EditorCamKind,
+    /// Camera of viewports which haven't picked one in their toolbar, decided by the game cameras on startup
+    #[serde(skip)]
+    default_cam: EditorCamKind,
+    viewport_cams: BTreeMap<u32, EditorCamKind>,
     pub show_ui: bool,
 }
 
 impl CameraWindowState {
+    /// Camera of the primary viewport
     pub fn editor_cam(&self) -> EditorCamKind {
-        self.editor_cam
+        self.viewport_cam(0)
+    }
+
+    pub fn viewport_cam(&self, viewport: u32) -> EditorCamKind {
+        self.viewport_cams
+            .get(&viewport)
+            .copied()
+            .unwrap_or(self.default_cam)
+    }
+
+    pub fn set_viewport_cam(&mut self, viewport: u32, editor_cam: EditorCamKind) {
+        self.viewport_cams.insert(viewport, editor_cam);
     }
 }
 
@@ -93,16 +142,19 @@ impl EditorWindow for CameraWindow {
     }
 
     fn viewport_toolbar_ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let viewport = cx.viewport();
         let state = cx.state_mut::<CameraWindow>().unwrap();
-        ui.menu_button(state.editor_cam.name(), |ui| {
+        ui.menu_button(state.viewport_cam(viewport).name(), |ui| {
             for camera in EditorCamKind::all() {
                 ui.horizontal(|ui| {
                     if ui.button(camera.name()).clicked() {
-                        if state.editor_cam != camera {
-                            set_active_editor_camera_marker(world, camera);
+                        if state.viewport_cam(viewport) != camera {
+                            world.send_event(EditorEvent::CameraSwitched {
+                                viewport,
+                                camera: camera.name(),
+                            });
                         }
-
-                        state.editor_cam = camera;
+                        state.set_viewport_cam(viewport, camera);
 
                         ui.close_menu();
                     }
@@ -112,15 +164,33 @@ impl EditorWindow for CameraWindow {
         ui.checkbox(&mut state.show_ui, "UI");
     }
 
+    fn save_state(state: &Self::State) -> Option<String> {
+        save_ron(state)
+    }
+    fn load_state(state: &mut Self::State, saved: &str) {
+        if let Some(saved) = load_ron::<CameraWindowState>(saved) {
+            state.viewport_cams = saved.viewport_cams;
+            state.show_ui = saved.show_ui;
+        }
+    }
+
     fn app_setup(app: &mut App) {
-        app.init_resource::<PreviouslyActiveCameras>();
+        app.init_resource::<PreviouslyActiveCameras>()
+            .init_resource::<ViewportRenderMode>()
+            .init_resource::<ViewportImages>();
 
         app.add_plugins(camera_2d_panzoom::PanCamPlugin)
             .add_plugins(camera_3d_free::FlycamPlugin)
             .add_plugins(camera_3d_panorbit::PanOrbitCameraPlugin)
             .add_systems(
                 Update,
-                set_editor_cam_active
+                (
+                    sync_viewport_cameras,
+                    apply_camera_settings,
+                    set_editor_cam_active,
+                    sync_active_editor_camera,
+                )
+                    .chain()
                     .before(camera_3d_panorbit::CameraSystem::EditorCam3dPanOrbit)
                     .before(camera_3d_free::CameraSystem::EditorCam3dFree)
                     .before(camera_2d_panzoom::CameraSystem::EditorCam2dPanZoom),
@@ -130,48 +200,22 @@ impl EditorWindow for CameraWindow {
             .add_systems(Update, initial_camera_setup);
         app.add_systems(PreStartup, spawn_editor_cameras);
 
+        app.add_status_bar_item(10, |editor: &Editor, _: &mut World, ui: &mut egui::Ui| {
+            if let Some(state) = editor.window_state::<CameraWindow>() {
+                let editor_cam = state.viewport_cam(editor.active_viewport());
+                ui.label(format!("Camera: {}", editor_cam.name()));
+            }
+        });
+
         app.add_systems(
             PostUpdate,
-            set_main_pass_viewport
+            (set_main_pass_viewport, update_viewport_images)
                 .after(bevy_editor_pls_core::EditorSet::UI)
                 .before(bevy::render::camera::CameraUpdateSystem),
         );
     }
 }
 
-fn set_active_editor_camera_marker(world: &mut World, editor_cam: EditorCamKind) {
-    let mut previously_active = world.query_filtered::<Entity, With<ActiveEditorCamera>>();
-    let mut previously_active_iter = previously_active.iter(world);
-    let previously_active = previously_active_iter.next();
-
-    assert!(
-        previously_active_iter.next().is_none(),
-        "there should be only one `ActiveEditorCamera`"
-    );
-
-    if let Some(previously_active) = previously_active {
-        world
-            .entity_mut(previously_active)
-            .remove::<ActiveEditorCamera>();
-    }
-
-    let entity = match editor_cam {
-        EditorCamKind::D2PanZoom => {
-            let mut state = world.query_filtered::<Entity, With<EditorCamera2dPanZoom>>();
-            state.iter(world).next().unwrap()
-        }
-        EditorCamKind::D3Free => {
-            let mut state = world.query_filtered::<Entity, With<EditorCamera3dFree>>();
-            state.iter(world).next().unwrap()
-        }
-        EditorCamKind::D3PanOrbit => {
-            let mut state = world.query_filtered::<Entity, With<EditorCamera3dPanOrbit>>();
-            state.iter(world).next().unwrap()
-        }
-    };
-    world.entity_mut(entity).insert(ActiveEditorCamera);
-}
-
 fn cameras_ui(ui: &mut egui::Ui, world: &mut World) {
     // let cameras = active_cameras.all_sorted();
     // let mut query: QueryState<&Camera> = world.query();
@@ -179,6 +223,16 @@ fn cameras_ui(ui: &mut egui::Ui, world: &mut World) {
     //
     // }
 
+    let mut render_mode = *world.resource::<ViewportRenderMode>();
+    ui.horizontal(|ui| {
+        ui.label("Render viewports to");
+        ui.selectable_value(&mut render_mode, ViewportRenderMode::Window, "Window");
+        ui.selectable_value(&mut render_mode, ViewportRenderMode::Image, "Image");
+    });
+    if render_mode != *world.resource::<ViewportRenderMode>() {
+        world.insert_resource(render_mode);
+    }
+
     let prev_cams = world.resource::<PreviouslyActiveCameras>();
 
     ui.label("Cameras");
@@ -201,19 +255,71 @@ fn cameras_ui(ui: &mut egui::Ui, world: &mut World) {
     }
 }
 
-fn spawn_editor_cameras(mut commands: Commands, editor: Res<Editor>) {
+/// Transforms and controller settings of the editor cameras of one viewport
+#[derive(Clone)]
+struct ViewportCameras {
+    free: (Transform, camera_3d_free::FlycamControls),
+    pan_orbit: (Transform, PanOrbitCamera),
+    pan_zoom: (
+        Transform,
+        camera_2d_panzoom::PanCamControls,
+        OrthographicProjection,
+    ),
+}
+
+impl Default for ViewportCameras {
+    fn default() -> Self {
+        ViewportCameras {
+            free: (Transform::from_xyz(0.0, 2.0, 5.0), default()),
+            pan_orbit: (Transform::from_xyz(0.0, 2.0, 5.0), default()),
+            pan_zoom: (
+                Transform::default(),
+                default(),
+                OrthographicProjection::default_2d(),
+            ),
+        }
+    }
+}
+
+fn spawn_editor_cameras(
+    mut commands: Commands,
+    editor: Res<Editor>,
+    settings: Res<EditorSettings>,
+) {
+    info!("Spawning editor cameras");
+
+    spawn_viewport_cameras(
+        &mut commands,
+        &settings,
+        editor.window(),
+        0,
+        ViewportCameras::default(),
+    );
+}
+
+/// The [`Camera::order`] of the first editor camera of `viewport`
+fn editor_camera_order(settings: &EditorSettings, viewport: u32) -> isize {
+    // every viewport has three cameras on the same window, which need distinct orders
+    settings.camera_order + 3 * viewport as isize
+}
+
+fn spawn_viewport_cameras(
+    commands: &mut Commands,
+    settings: &EditorSettings,
+    window: Entity,
+    viewport: u32,
+    cameras: ViewportCameras,
+) {
     #[derive(Component, Default)]
     struct Ec2d;
     #[derive(Component, Default)]
     struct Ec3d;
 
-    info!("Spawning editor cameras");
+    let render_layers = RenderLayers::default().with(settings.render_layer);
 
-    let render_layers = RenderLayers::default().with(EDITOR_RENDER_LAYER);
+    let editor_cam_priority = editor_camera_order(settings, viewport);
 
-    let editor_cam_priority = 100;
-
-    let target = RenderTarget::Window(WindowRef::Entity(editor.window()));
+    let target = RenderTarget::Window(WindowRef::Entity(window));
 
     commands.spawn((
         Camera3d::default(),
@@ -223,15 +329,15 @@ fn spawn_editor_cameras(mut commands: Commands, editor: Res<Editor>) {
             target: target.clone(),
             ..default()
         },
-        Transform::from_xyz(0.0, 2.0, 5.0),
+        cameras.free.0,
         Ec3d,
-        camera_3d_free::FlycamControls::default(),
+        cameras.free.1,
         EditorCamera,
         EditorCamera3dFree,
+        EditorViewport(viewport),
         HideInEditor,
         Name::new("Editor Camera 3D Free"),
         NotInScene,
-        GizmoCamera,
         render_layers.clone(),
     ));
 
@@ -244,15 +350,15 @@ fn spawn_editor_cameras(mut commands: Commands, editor: Res<Editor>) {
             is_active: false,
             ..default()
         },
-        Transform::from_xyz(0.0, 2.0, 5.0),
+        cameras.pan_orbit.0,
         Ec3d,
-        PanOrbitCamera::default(),
+        cameras.pan_orbit.1,
         EditorCamera,
         EditorCamera3dPanOrbit,
+        EditorViewport(viewport),
         HideInEditor,
         Name::new("Editor Camera 3D Pan/Orbit"),
         NotInScene,
-        GizmoCamera,
         render_layers.clone(),
     ));
 
@@ -265,57 +371,208 @@ fn spawn_editor_cameras(mut commands: Commands, editor: Res<Editor>) {
             is_active: false,
             ..default()
         },
+        cameras.pan_zoom.0,
+        cameras.pan_zoom.2,
         Ec2d,
-        camera_2d_panzoom::PanCamControls::default(),
+        cameras.pan_zoom.1,
         EditorCamera,
         EditorCamera2dPanZoom,
+        EditorViewport(viewport),
         HideInEditor,
         Name::new("Editor Camera 2D Pan/Zoom"),
         NotInScene,
-        GizmoCamera,
         render_layers,
     ));
 }
 
+/// Spawns editor cameras for newly opened viewports, starting with the view of the primary viewport,
+/// and despawns the cameras of closed viewports.
+#[allow(clippy::too_many_arguments)]
+fn sync_viewport_cameras(
+    mut commands: Commands,
+    editor: Res<Editor>,
+    settings: Res<EditorSettings>,
+    internal_state: Res<EditorInternalState>,
+    cameras: Query<(Entity, &EditorViewport), With<EditorCamera>>,
+    free_cams: Query<(&EditorViewport, &Transform, &camera_3d_free::FlycamControls)>,
+    pan_orbit_cams: Query<(&EditorViewport, &Transform, &PanOrbitCamera)>,
+    pan_zoom_cams: Query<(
+        &EditorViewport,
+        &Transform,
+        &camera_2d_panzoom::PanCamControls,
+        &OrthographicProjection,
+    )>,
+) {
+    let mut has_cameras = HashSet::new();
+    for (entity, &EditorViewport(viewport)) in &cameras {
+        if viewport != 0 && !internal_state.contains_viewport(viewport) {
+            commands.entity(entity).despawn();
+        } else {
+            has_cameras.insert(viewport);
+        }
+    }
+
+    let new_viewports: Vec<u32> = editor
+        .viewports()
+        .map(|(viewport, _)| viewport)
+        .filter(|viewport| !has_cameras.contains(viewport))
+        .collect();
+    if new_viewports.is_empty() {
+        return;
+    }
+
+    let is_primary = |viewport: &EditorViewport| viewport.0 == 0;
+    let mut template = ViewportCameras::default();
+    if let Some((_, transform, controls)) = free_cams.iter().find(|(v, ..)| is_primary(v)) {
+        template.free = (*transform, controls.clone());
+    }
+    if let Some((_, transform, controls)) = pan_orbit_cams.iter().find(|(v, ..)| is_primary(v)) {
+        template.pan_orbit = (*transform, controls.clone());
+    }
+    if let Some((_, transform, controls, projection)) =
+        pan_zoom_cams.iter().find(|(v, ..)| is_primary(v))
+    {
+        template.pan_zoom = (*transform, controls.clone(), projection.clone());
+    }
+
+    for viewport in new_viewports {
+        spawn_viewport_cameras(
+            &mut commands,
+            &settings,
+            editor.window(),
+            viewport,
+            template.clone(),
+        );
+    }
+}
+
+/// Moves the existing editor cameras to the render layer and orders of changed [`EditorSettings`]
+fn apply_camera_settings(
+    settings: Res<EditorSettings>,
+    mut cameras: Query<
+        (
+            &EditorViewport,
+            &mut Camera,
+            &mut RenderLayers,
+            Has<EditorCamera3dPanOrbit>,
+            Has<EditorCamera2dPanZoom>,
+        ),
+        With<EditorCamera>,
+    >,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for (&EditorViewport(viewport), mut camera, mut render_layers, pan_orbit, pan_zoom) in
+        &mut cameras
+    {
+        // same offsets as in `spawn_viewport_cameras`
+        let offset = if pan_zoom { 2 } else { pan_orbit as isize };
+        let order = editor_camera_order(&settings, viewport) + offset;
+        if camera.order != order {
+            camera.order = order;
+        }
+        let layers = RenderLayers::default().with(settings.render_layer);
+        if *render_layers != layers {
+            *render_layers = layers;
+        }
+    }
+}
+
 fn set_editor_cam_active(
     editor: Res<Editor>,
 
     mut editor_cameras: ParamSet<(
-        Query<(&mut Camera, &mut camera_3d_free::FlycamControls)>,
-        Query<(&mut Camera, &mut camera_3d_panorbit::PanOrbitCamera)>,
-        Query<(&mut Camera, &mut camera_2d_panzoom::PanCamControls)>,
+        Query<(
+            &EditorViewport,
+            &mut Camera,
+            &mut camera_3d_free::FlycamControls,
+        )>,
+        Query<(
+            &EditorViewport,
+            &mut Camera,
+            &mut camera_3d_panorbit::PanOrbitCamera,
+        )>,
+        Query<(
+            &EditorViewport,
+            &mut Camera,
+            &mut camera_2d_panzoom::PanCamControls,
+        )>,
     )>,
     // mut ui_camera_settings: Query<&mut UiCameraConfig, With<EditorCamera>>,
 ) {
     let camera_window_state = &editor.window_state::<CameraWindow>().unwrap();
-    let editor_cam = camera_window_state.editor_cam;
 
     // if editor.active() {
     // ui_camera_settings
     // .for_each_mut(|mut settings| settings.show_ui = camera_window_state.show_ui);
     // }
 
-    {
-        let mut q = editor_cameras.p0();
-        let mut editor_cam_3d_free = q.single_mut();
-        let active = matches!(editor_cam, EditorCamKind::D3Free) && editor.active();
-        editor_cam_3d_free.0.is_active = active;
-        editor_cam_3d_free.1.enable_movement = active && !editor.listening_for_text();
-        editor_cam_3d_free.1.enable_look = active && editor.viewport_interaction_active();
+    // only cameras of visible viewports render, and only the active viewport receives input.
+    // whether the pointer is on the ui is checked by the run conditions of the camera systems
+    let is_active = |viewport: u32, editor_cam: EditorCamKind| {
+        camera_window_state.viewport_cam(viewport) == editor_cam
+            && editor.active()
+            && editor.viewport_rect(viewport).is_some()
+    };
+    let has_input = |viewport: u32| viewport == editor.active_viewport();
+
+    for (&EditorViewport(viewport), mut camera, mut controls) in editor_cameras.p0().iter_mut() {
+        let active = is_active(viewport, EditorCamKind::D3Free);
+        camera.is_active = active;
+        controls.enable_movement = active && has_input(viewport) && !editor.listening_for_text();
+        controls.enable_look = active && has_input(viewport);
     }
-    {
-        let mut q = editor_cameras.p1();
-        let mut editor_cam_3d_panorbit = q.single_mut();
-        let active = matches!(editor_cam, EditorCamKind::D3PanOrbit) && editor.active();
-        editor_cam_3d_panorbit.0.is_active = active;
-        editor_cam_3d_panorbit.1.enabled = active && editor.viewport_interaction_active();
+    for (&EditorViewport(viewport), mut camera, mut controls) in editor_cameras.p1().iter_mut() {
+        let active = is_active(viewport, EditorCamKind::D3PanOrbit);
+        camera.is_active = active;
+        controls.enabled = active && has_input(viewport);
     }
-    {
-        let mut q = editor_cameras.p2();
-        let mut editor_cam_2d_panzoom = q.single_mut();
-        let active = matches!(editor_cam, EditorCamKind::D2PanZoom) && editor.active();
-        editor_cam_2d_panzoom.0.is_active = active;
-        editor_cam_2d_panzoom.1.enabled = active && editor.viewport_interaction_active();
+    for (&EditorViewport(viewport), mut camera, mut controls) in editor_cameras.p2().iter_mut() {
+        let active = is_active(viewport, EditorCamKind::D2PanZoom);
+        camera.is_active = active;
+        controls.enabled = active && has_input(viewport);
+    }
+}
+
+/// Moves the [`ActiveEditorCamera`] and [`GizmoCamera`] markers to the current camera of the active viewport
+fn sync_active_editor_camera(
+    mut commands: Commands,
+    editor: Res<Editor>,
+    cameras: Query<
+        (
+            Entity,
+            &EditorViewport,
+            Has<EditorCamera3dFree>,
+            Has<EditorCamera3dPanOrbit>,
+            Has<ActiveEditorCamera>,
+        ),
+        With<EditorCamera>,
+    >,
+) {
+    let Some(camera_window_state) = editor.window_state::<CameraWindow>() else {
+        return;
+    };
+    let active_viewport = editor.active_viewport();
+    let editor_cam = camera_window_state.viewport_cam(active_viewport);
+
+    for (entity, &EditorViewport(viewport), free, pan_orbit, was_active) in &cameras {
+        let kind = match (free, pan_orbit) {
+            (true, _) => EditorCamKind::D3Free,
+            (_, true) => EditorCamKind::D3PanOrbit,
+            _ => EditorCamKind::D2PanZoom,
+        };
+        let active = viewport == active_viewport && kind == editor_cam;
+        if active && !was_active {
+            commands
+                .entity(entity)
+                .insert((ActiveEditorCamera, GizmoCamera));
+        } else if !active && was_active {
+            commands
+                .entity(entity)
+                .remove::<(ActiveEditorCamera, GizmoCamera)>();
+        }
     }
 }
 
@@ -374,13 +631,15 @@ fn focus_selected(
         Without<ActiveEditorCamera>,
     >,
     editor: Res<Editor>,
+    settings: Res<EditorSettings>,
     window: Query<&Window>,
+    mut notifications: ResMut<Notifications>,
 ) {
-    let Ok(window) = window.get(editor.window()) else {
+    if window.get(editor.window()).is_err() {
         //Prevent accumulation of irrelevant events
         editor_events.clear();
         return;
-    };
+    }
 
     for event in editor_events.read() {
         match *event {
@@ -388,9 +647,11 @@ fn focus_selected(
             _ => continue,
         }
 
-        let hierarchy = editor.window_state::<HierarchyWindow>().unwrap();
+        let Some(hierarchy) = editor.window_state::<HierarchyWindow>() else {
+            return;
+        };
         if hierarchy.selected.is_empty() {
-            info!("Coudldn't focus on selection because selection is empty");
+            notifications.info("Couldn't focus on selection because selection is empty");
             return;
         }
 
@@ -422,23 +683,25 @@ fn focus_selected(
                 |(acc_min, acc_max), (min, max)| (acc_min.min(min), acc_max.max(max)),
             );
 
-        const RADIUS_MULTIPLIER: f32 = 2.0;
-
         let bounds_size = bounds_max - bounds_min;
         let focus_loc = bounds_min + bounds_size * 0.5;
         let radius = if bounds_size.max_element() > f32::EPSILON {
-            bounds_size.length() * RADIUS_MULTIPLIER
+            bounds_size.length() * settings.focus_radius_multiplier
         } else {
-            RADIUS_MULTIPLIER
+            settings.focus_radius_multiplier
         };
 
-        let (mut camera_tf, pan_orbit_cam, ortho) = active_cam.single_mut();
+        // the active camera belongs to the viewport under the pointer
+        let Ok((mut camera_tf, pan_orbit_cam, ortho)) = active_cam.get_single_mut() else {
+            return;
+        };
 
         if let Some(mut ortho) = ortho {
             camera_tf.translation.x = focus_loc.x;
             camera_tf.translation.y = focus_loc.y;
 
-            ortho.scale = radius / window.width().min(window.height()).max(1.0);
+            let viewport = editor.viewport();
+            ortho.scale = radius / viewport.width().min(viewport.height()).max(1.0);
         } else {
             camera_tf.translation = focus_loc + camera_tf.rotation.mul_vec3(Vec3::Z) * radius;
         }
@@ -450,7 +713,7 @@ fn focus_selected(
 
         let len = hierarchy.selected.len();
         let noun = if len == 1 { "entity" } else { "entities" };
-        info!("Focused on {} {}", len, noun);
+        notifications.info(format!("Focused on {} {}", len, noun));
     }
 }
 
@@ -459,24 +722,16 @@ fn initial_camera_setup(
     mut was_positioned_3d: Local<bool>,
     mut was_positioned_2d: Local<bool>,
 
-    mut commands: Commands,
     mut editor: ResMut<Editor>,
 
     mut cameras: ParamSet<(
         // 2d pan/zoom
-        Query<(Entity, &mut Transform), With<EditorCamera2dPanZoom>>,
+        Query<&mut Transform, With<EditorCamera2dPanZoom>>,
         // 3d free
-        Query<
-            (Entity, &mut Transform, &mut camera_3d_free::FlycamControls),
-            With<EditorCamera3dFree>,
-        >,
+        Query<(&mut Transform, &mut camera_3d_free::FlycamControls), With<EditorCamera3dFree>>,
         // 3d pan/orbit
         Query<
-            (
-                Entity,
-                &mut Transform,
-                &mut camera_3d_panorbit::PanOrbitCamera,
-            ),
+            (&mut Transform, &mut camera_3d_panorbit::PanOrbitCamera),
             With<EditorCamera3dPanOrbit>,
         >,
         Query<&Transform, (With<Camera2d>, Without<EditorCamera>)>,
@@ -489,85 +744,62 @@ fn initial_camera_setup(
     if !*has_decided_initial_cam {
         let camera_state = editor.window_state_mut::<CameraWindow>().unwrap();
 
-        match (cam2d.is_some(), cam3d.is_some()) {
-            (true, false) => {
-                camera_state.editor_cam = EditorCamKind::D2PanZoom;
-                commands
-                    .entity(cameras.p0().single().0)
-                    .insert(ActiveEditorCamera);
-                *has_decided_initial_cam = true;
-            }
-            (false, true) => {
-                camera_state.editor_cam = EditorCamKind::D3PanOrbit;
-                commands
-                    .entity(cameras.p2().single().0)
-                    .insert(ActiveEditorCamera);
-                *has_decided_initial_cam = true;
-            }
-            (true, true) => {
-                camera_state.editor_cam = EditorCamKind::D3PanOrbit;
-                commands
-                    .entity(cameras.p2().single().0)
-                    .insert(ActiveEditorCamera);
-                *has_decided_initial_cam = true;
-            }
+        camera_state.default_cam = match (cam2d.is_some(), cam3d.is_some()) {
+            (true, false) => EditorCamKind::D2PanZoom,
+            (false, true) | (true, true) => EditorCamKind::D3PanOrbit,
             (false, false) => return,
-        }
+        };
+        *has_decided_initial_cam = true;
     }
 
-    if !*was_positioned_2d {
-        if let Some(cam2d_transform) = cam2d {
-            if !cam2d_transform.rotation.is_finite()
-                || !cam2d_transform.translation.is_finite()
-                || !cam2d_transform.scale.is_finite()
-            {
-                return;
-            };
+    // the cameras of all viewports start out at the position of the game camera
+    if !*was_positioned_2d && let Some(cam2d_transform) = cam2d {
+        if !cam2d_transform.rotation.is_finite()
+            || !cam2d_transform.translation.is_finite()
+            || !cam2d_transform.scale.is_finite()
+        {
+            return;
+        };
 
-            let mut query = cameras.p0();
-            let (_, mut cam_transform) = query.single_mut();
+        for mut cam_transform in cameras.p0().iter_mut() {
             *cam_transform = cam2d_transform;
-
-            *was_positioned_2d = true;
         }
+
+        *was_positioned_2d = true;
     }
 
-    if !*was_positioned_3d {
-        if let Some(cam3d_transform) = cam3d {
-            if !cam3d_transform.rotation.is_finite()
-                || !cam3d_transform.translation.is_finite()
-                || !cam3d_transform.scale.is_finite()
-            {
-                return;
-            };
-
-            {
-                let mut query = cameras.p1();
-                let (_, mut cam_transform, mut cam) = query.single_mut();
-                *cam_transform = cam3d_transform;
-                let (yaw, pitch, _) = cam3d_transform.rotation.to_euler(EulerRot::YXZ);
-                cam.yaw = yaw;
-                cam.pitch = pitch;
-            }
+    if !*was_positioned_3d && let Some(cam3d_transform) = cam3d {
+        if !cam3d_transform.rotation.is_finite()
+            || !cam3d_transform.translation.is_finite()
+            || !cam3d_transform.scale.is_finite()
+        {
+            return;
+        };
 
-            {
-                let mut query = cameras.p2();
-                let (_, mut cam_transform, mut cam) = query.single_mut();
-                cam.radius = cam3d_transform.translation.distance(cam.focus);
-                *cam_transform = cam3d_transform;
-            }
+        for (mut cam_transform, mut cam) in cameras.p1().iter_mut() {
+            *cam_transform = cam3d_transform;
+            let (yaw, pitch, _) = cam3d_transform.rotation.to_euler(EulerRot::YXZ);
+            cam.yaw = yaw;
+            cam.pitch = pitch;
+        }
 
-            *was_positioned_3d = true;
+        for (mut cam_transform, mut cam) in cameras.p2().iter_mut() {
+            cam.radius = cam3d_transform.translation.distance(cam.focus);
+            *cam_transform = cam3d_transform;
         }
+
+        *was_positioned_3d = true;
     }
 }
 
 fn set_main_pass_viewport(
     editor: Res<Editor>,
-    window: Query<(&bevy_inspector_egui::bevy_egui::EguiSettings, &Window)>,
-    mut cameras: Query<&mut Camera, With<EditorCamera>>,
+    render_mode: Res<ViewportRenderMode>,
+    window: Query<(&EguiSettings, &Window)>,
+    mut cameras: Query<(&EditorViewport, &mut Camera), With<EditorCamera>>,
 ) {
-    if !editor.is_changed() {
+    // offscreen images are rendered as a whole, see `update_viewport_images`
+    if !editor.is_changed() || *render_mode == ViewportRenderMode::Image {
         return;
     };
 
@@ -575,11 +807,10 @@ fn set_main_pass_viewport(
         return;
     };
 
-    let viewport = editor.active().then(|| {
-        let scale_factor = window.scale_factor() * egui_settings.scale_factor;
-
-        let mut viewport_pos = editor.viewport().left_top().to_vec2() * scale_factor;
-        let mut viewport_size = editor.viewport().size() * scale_factor;
+    let scale_factor = window.scale_factor() * egui_settings.scale_factor;
+    let physical_viewport = |rect: egui::Rect| {
+        let mut viewport_pos = rect.left_top().to_vec2() * scale_factor;
+        let mut viewport_size = rect.size() * scale_factor;
 
         if !viewport_size.is_finite() {
             warn!("editor viewport size is infinite");
@@ -595,9 +826,152 @@ fn set_main_pass_viewport(
             ),
             depth: 0.0..1.0,
         }
+    };
+
+    for (&EditorViewport(viewport), mut cam) in cameras.iter_mut() {
+        let viewport = editor
+            .viewport_rect(viewport)
+            .filter(|_| editor.active())
+            .map(physical_viewport);
+        cam.viewport.clone_from(&viewport);
+    }
+}
+
+/// Maps a position in egui coordinates inside of `viewport` to the logical viewport coordinates of `camera`,
+/// as expected by [`Camera::viewport_to_world`].
+///
+/// This works for both [`ViewportRenderMode`]s, so it should be used instead of the window cursor position for picking.
+pub fn egui_to_viewport_position(
+    editor: &Editor,
+    camera: &Camera,
+    viewport: u32,
+    pos: egui::Pos2,
+) -> Option<Vec2> {
+    rect_to_viewport_position(editor.viewport_rect(viewport)?, camera, pos)
+}
+
+/// Like [`egui_to_viewport_position`], for a camera rendering to the viewport at `rect`
+pub(crate) fn rect_to_viewport_position(
+    rect: egui::Rect,
+    camera: &Camera,
+    pos: egui::Pos2,
+) -> Option<Vec2> {
+    if !rect.contains(pos) {
+        return None;
+    }
+    let normalized = (pos - rect.min) / rect.size();
+    let viewport_size = camera.logical_viewport_size()?;
+    Some(Vec2::new(normalized.x, normalized.y) * viewport_size)
+}
+
+fn viewport_image(size: Extent3d) -> Image {
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("editor_viewport"),
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..default()
+    };
+    image.resize(size);
+    image
+}
+
+/// Creates, resizes and removes the offscreen images of the viewports in [`ViewportRenderMode::Image`],
+/// and points the editor cameras either at them or back at the window.
+#[allow(clippy::too_many_arguments)]
+fn update_viewport_images(
+    mut editor: ResMut<Editor>,
+    render_mode: Res<ViewportRenderMode>,
+    internal_state: Res<EditorInternalState>,
+    window: Query<(&EguiSettings, &Window)>,
+    mut viewport_images: ResMut<ViewportImages>,
+    mut images: ResMut<Assets<Image>>,
+    mut egui_user_textures: ResMut<EguiUserTextures>,
+    mut gizmo_options: Option<ResMut<GizmoOptions>>,
+    mut cameras: Query<(&EditorViewport, &mut Camera), With<EditorCamera>>,
+) {
+    let render_to_image = *render_mode == ViewportRenderMode::Image;
+
+    viewport_images.0.retain(|&viewport, image| {
+        let keep = render_to_image && internal_state.contains_viewport(viewport);
+        if !keep {
+            egui_user_textures.remove_image(image);
+            editor.set_viewport_texture(viewport, None);
+        }
+        keep
     });
 
-    cameras
-        .iter_mut()
-        .for_each(|mut cam| cam.viewport.clone_from(&viewport));
+    let Ok((egui_settings, window)) = window.get(editor.window()) else {
+        return;
+    };
+
+    if !render_to_image {
+        if render_mode.is_changed() {
+            let target = RenderTarget::Window(WindowRef::Entity(editor.window()));
+            for (_, mut camera) in cameras.iter_mut() {
+                camera.target = target.clone();
+            }
+            if let Some(gizmo_options) = &mut gizmo_options {
+                gizmo_options.viewport_rect = None;
+            }
+        }
+        return;
+    }
+
+    let scale_factor = window.scale_factor() * egui_settings.scale_factor;
+    let viewports: Vec<(u32, egui::Rect)> = editor.viewports().collect();
+    for (viewport, rect) in viewports {
+        let size = Extent3d {
+            width: ((rect.width() * scale_factor) as u32).max(1),
+            height: ((rect.height() * scale_factor) as u32).max(1),
+            depth_or_array_layers: 1,
+        };
+
+        let image = viewport_images
+            .0
+            .entry(viewport)
+            .or_insert_with(|| images.add(viewport_image(size)));
+        // only touch the image and the editor when something changed, so they aren't marked as changed every frame
+        let resize = images
+            .get(&*image)
+            .is_some_and(|image| image.texture_descriptor.size != size);
+        if resize && let Some(image) = images.get_mut(&*image) {
+            image.resize(size);
+        }
+
+        if editor.viewport_texture(viewport).is_none() {
+            let texture = egui_user_textures.add_image(image.clone());
+            editor.set_viewport_texture(viewport, Some(texture));
+        }
+
+        for (&EditorViewport(camera_viewport), mut camera) in cameras.iter_mut() {
+            let targets_image =
+                matches!(&camera.target, RenderTarget::Image(current) if current == &*image);
+            if camera_viewport == viewport && !targets_image {
+                camera.target = RenderTarget::Image(image.clone());
+                camera.viewport = None;
+            }
+        }
+    }
+
+    // the gizmo can't infer the viewport from the camera, since it renders to the whole image
+    if let Some(gizmo_options) = &mut gizmo_options {
+        let rect = editor.viewport();
+        let (min, max) = (
+            rect.min * egui_settings.scale_factor,
+            rect.max * egui_settings.scale_factor,
+        );
+        let viewport_rect = Some(Rect::new(min.x, min.y, max.x, max.y));
+        if gizmo_options.viewport_rect != viewport_rect {
+            gizmo_options.viewport_rect = viewport_rect;
+        }
+    }
 }
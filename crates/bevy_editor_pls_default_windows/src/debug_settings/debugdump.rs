@@ -71,10 +71,9 @@ pub fn execute_dot(dot: &str, format: &str) -> Result<Vec<u8>, std::io::Error> {
 
     let output = child.wait_with_output()?;
     if !output.status.success() {
-        return Err(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            String::from_utf8_lossy(&output.stderr),
-        ));
+        return Err(std::io::Error::other(String::from_utf8_lossy(
+            &output.stderr,
+        )));
     }
 
     Ok(output.stdout)
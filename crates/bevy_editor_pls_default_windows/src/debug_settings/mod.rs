@@ -6,18 +6,22 @@ use bevy::{
     reflect::TypeRegistry,
     render::{render_resource::WgpuFeatures, renderer::RenderAdapter},
 };
-use bevy_editor_pls_core::editor_window::EditorWindow;
+use bevy_editor_pls_core::editor_window::{load_ron, save_ron, EditorWindow};
+use bevy_editor_pls_core::{Editor, Notifications};
 use bevy_inspector_egui::{
     egui::{self, Grid},
     reflect_inspector::ui_for_value,
 };
+use serde::{Deserialize, Serialize};
 
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub struct DebugSettingsWindowState {
+    /// Not persisted, so that the game doesn't start paused
+    #[serde(skip)]
     pub pause_time: bool,
     pub wireframes: bool,
     pub highlight_selected: bool,
-
-    open_debugdump_status: Option<DebugdumpError>,
 }
 
 enum DebugdumpError {
@@ -27,14 +31,26 @@ enum DebugdumpError {
     IO(std::io::Error),
 }
 
+impl std::fmt::Display for DebugdumpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DebugdumpError::DotNotFound => write!(
+                f,
+                "Could not generate svg. Make sure to install the `dot` program from https://graphviz.org/download/ and make it available in your PATH."
+            ),
+            DebugdumpError::ScheduleNotFound => write!(f, "Schedule does not exist"),
+            DebugdumpError::OpenError(e) => e.fmt(f),
+            DebugdumpError::IO(e) => e.fmt(f),
+        }
+    }
+}
+
 impl Default for DebugSettingsWindowState {
     fn default() -> Self {
         Self {
             pause_time: false,
             wireframes: false,
             highlight_selected: true,
-
-            open_debugdump_status: None,
         }
     }
 }
@@ -56,11 +72,54 @@ impl EditorWindow for DebugSettingsWindow {
         debug_ui(world, state, ui, &type_registry);
     }
 
+    fn save_state(state: &Self::State) -> Option<String> {
+        save_ron(state)
+    }
+    fn load_state(state: &mut Self::State, saved: &str) {
+        if let Some(saved) = load_ron(saved) {
+            *state = saved;
+        }
+    }
+
+    fn app_setup(app: &mut App) {
+        app.add_systems(Startup, apply_restored_settings);
+    }
+
     fn app_finish(app: &mut App) {
         debugdump::setup(app);
     }
 }
 
+/// The ui only applies settings when they are changed, so the ones restored from the last run are applied on startup
+fn apply_restored_settings(
+    mut commands: Commands,
+    editor: Res<Editor>,
+    adapter: Option<Res<RenderAdapter>>,
+    wireframe_config: Option<ResMut<WireframeConfig>>,
+) {
+    let state = editor.window_state::<DebugSettingsWindow>().unwrap();
+    // the settings may have been saved on a machine whose adapter supports wireframes
+    if !state.wireframes || !supports_wireframes(adapter.as_deref()) {
+        return;
+    }
+    match wireframe_config {
+        Some(mut wireframe_config) => wireframe_config.global = true,
+        None => commands.insert_resource(WireframeConfig {
+            global: true,
+            ..default()
+        }),
+    }
+}
+
+fn supports_wireframes(adapter: Option<&RenderAdapter>) -> bool {
+    adapter.is_some_and(|adapter| {
+        adapter
+            .0
+            .features()
+            .contains(WgpuFeatures::POLYGON_MODE_LINE)
+    })
+}
+
 fn debug_ui(
     world: &mut World,
     state: &mut DebugSettingsWindowState,
@@ -77,7 +136,7 @@ fn debug_ui(
             ui.separator();
         }
 
-        debug_ui_debugdump(world, state, ui);
+        debug_ui_debugdump(world, ui);
     });
 }
 
@@ -104,6 +163,8 @@ fn debug_ui_options(
 
         let mut time = world.resource_mut::<Time<Virtual>>();
 
+        // time can also be paused from the toolbar or by stepping frames
+        state.pause_time = time.is_paused();
         if ui.checkbox(&mut state.pause_time, "").changed() {
             if state.pause_time {
                 time.pause();
@@ -127,14 +188,7 @@ fn debug_ui_options(
         }
         ui.end_row();
 
-        let wireframe_enabled = world
-            .get_resource::<RenderAdapter>()
-            .map_or(false, |adapter| {
-                adapter
-                    .0
-                    .features()
-                    .contains(WgpuFeatures::POLYGON_MODE_LINE)
-            });
+        let wireframe_enabled = supports_wireframes(world.get_resource::<RenderAdapter>());
 
         if wireframe_enabled {
             ui.label("Wireframes");
@@ -162,7 +216,7 @@ fn debug_ui_options(
     });
 }
 
-fn debug_ui_debugdump(world: &mut World, state: &mut DebugSettingsWindowState, ui: &mut egui::Ui) {
+fn debug_ui_debugdump(world: &mut World, ui: &mut egui::Ui) {
     let open_dot = |dot: &Option<String>, path: &str| -> Result<(), DebugdumpError> {
         let dot = dot.as_ref().ok_or(DebugdumpError::ScheduleNotFound)?;
 
@@ -184,13 +238,13 @@ fn debug_ui_debugdump(world: &mut World, state: &mut DebugSettingsWindowState, u
         if ui.button("Open `Update` schedule").clicked() {
             let schedule_graph = world.get_resource::<debugdump::DotGraphs>().unwrap();
             if let Err(e) = open_dot(&schedule_graph.update_schedule, "schedule_main") {
-                state.open_debugdump_status = Some(e);
+                world.resource_mut::<Notifications>().error(e.to_string());
             }
         }
         if ui.button("Open `FixedUpdate` schedule").clicked() {
             let schedule_graph = world.get_resource::<debugdump::DotGraphs>().unwrap();
             if let Err(e) = open_dot(&schedule_graph.fixed_update_schedule, "schedule_fixed") {
-                state.open_debugdump_status = Some(e);
+                world.resource_mut::<Notifications>().error(e.to_string());
             }
         }
         if ui.button("Open render extract schedule").clicked() {
@@ -199,41 +253,20 @@ fn debug_ui_debugdump(world: &mut World, state: &mut DebugSettingsWindowState, u
                 &schedule_graph.render_extract_schedule,
                 "schedule_render_extract",
             ) {
-                state.open_debugdump_status = Some(e);
+                world.resource_mut::<Notifications>().error(e.to_string());
             }
         }
         if ui.button("Open render main schedule").clicked() {
             let schedule_graph = world.get_resource::<debugdump::DotGraphs>().unwrap();
             if let Err(e) = open_dot(&schedule_graph.render_main_schedule, "schedule_render_main") {
-                state.open_debugdump_status = Some(e);
+                world.resource_mut::<Notifications>().error(e.to_string());
             }
         }
         if ui.button("Open render graph").clicked() {
             let schedule_graph = world.get_resource::<debugdump::DotGraphs>().unwrap();
             if let Err(e) = open_dot(&schedule_graph.render_graph, "render_graph") {
-                state.open_debugdump_status = Some(e);
+                world.resource_mut::<Notifications>().error(e.to_string());
             }
         }
     });
-
-    if let Some(error) = &state.open_debugdump_status {
-        let msg = match error {
-            DebugdumpError::DotNotFound => {
-                ui.vertical(|ui| {
-                    ui.label("Could not generate svg.");
-                    ui.label("Make sure to install the `dot` program from");
-                    ui.hyperlink("https://graphviz.org/download/");
-                    ui.label("and make it available in your PATH.");
-                });
-                return;
-            }
-            DebugdumpError::OpenError(e) => e.to_string(),
-            DebugdumpError::IO(e) => e.to_string(),
-            DebugdumpError::ScheduleNotFound => {
-                ui.label("Schedule does not exist");
-                return;
-            }
-        };
-        ui.label(egui::RichText::new(msg).color(egui::Color32::RED));
-    }
 }
@@ -1,18 +1,18 @@
 use bevy::{
-    ecs::{query::QueryFilter, system::RunSystemOnce},
+    ecs::{entity::EntityHashMap, query::QueryFilter},
     prelude::*,
     render::view::RenderLayers,
 };
 
-use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
+use bevy_editor_pls_core::editor_window::{load_ron, save_ron, EditorWindow, EditorWindowContext};
+use bevy_editor_pls_core::undo::{ReflectComponentChange, UndoStack};
+use bevy_editor_pls_core::{Editor, EditorSet, EditorSettings};
 use bevy_inspector_egui::egui;
+use serde::{Deserialize, Serialize};
 use transform_gizmo_bevy::GizmoTarget;
 use transform_gizmo_bevy::{EnumSet, GizmoMode};
 
-use crate::{
-    cameras::{EditorCamera, EDITOR_RENDER_LAYER},
-    hierarchy::HierarchyWindow,
-};
+use crate::{cameras::EditorCamera, hierarchy::HierarchyWindow, scenes::NotInScene};
 
 pub struct GizmoState {
     /// If [false], doesn't show any gizmos
@@ -30,6 +30,91 @@ impl Default for GizmoState {
     }
 }
 
+/// The persisted part of [`GizmoState`]
+#[derive(Serialize, Deserialize)]
+struct SavedGizmoState {
+    camera_gizmo_active: bool,
+    gizmo_modes: Vec<SavedGizmoMode>,
+}
+
+/// Serializable mirror of [`GizmoMode`], which doesn't implement serde's traits.
+///
+/// Mapping the variants explicitly means an upstream rename fails to compile instead of silently dropping saved modes.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+enum SavedGizmoMode {
+    RotateView,
+    RotateX,
+    RotateY,
+    RotateZ,
+    TranslateView,
+    TranslateX,
+    TranslateY,
+    TranslateZ,
+    TranslateXY,
+    TranslateXZ,
+    TranslateYZ,
+    ScaleUniform,
+    ScaleX,
+    ScaleY,
+    ScaleZ,
+    ScaleXY,
+    ScaleXZ,
+    ScaleYZ,
+    Arcball,
+}
+
+impl From<GizmoMode> for SavedGizmoMode {
+    fn from(mode: GizmoMode) -> Self {
+        match mode {
+            GizmoMode::RotateView => SavedGizmoMode::RotateView,
+            GizmoMode::RotateX => SavedGizmoMode::RotateX,
+            GizmoMode::RotateY => SavedGizmoMode::RotateY,
+            GizmoMode::RotateZ => SavedGizmoMode::RotateZ,
+            GizmoMode::TranslateView => SavedGizmoMode::TranslateView,
+            GizmoMode::TranslateX => SavedGizmoMode::TranslateX,
+            GizmoMode::TranslateY => SavedGizmoMode::TranslateY,
+            GizmoMode::TranslateZ => SavedGizmoMode::TranslateZ,
+            GizmoMode::TranslateXY => SavedGizmoMode::TranslateXY,
+            GizmoMode::TranslateXZ => SavedGizmoMode::TranslateXZ,
+            GizmoMode::TranslateYZ => SavedGizmoMode::TranslateYZ,
+            GizmoMode::ScaleUniform => SavedGizmoMode::ScaleUniform,
+            GizmoMode::ScaleX => SavedGizmoMode::ScaleX,
+            GizmoMode::ScaleY => SavedGizmoMode::ScaleY,
+            GizmoMode::ScaleZ => SavedGizmoMode::ScaleZ,
+            GizmoMode::ScaleXY => SavedGizmoMode::ScaleXY,
+            GizmoMode::ScaleXZ => SavedGizmoMode::ScaleXZ,
+            GizmoMode::ScaleYZ => SavedGizmoMode::ScaleYZ,
+            GizmoMode::Arcball => SavedGizmoMode::Arcball,
+        }
+    }
+}
+
+impl From<SavedGizmoMode> for GizmoMode {
+    fn from(mode: SavedGizmoMode) -> Self {
+        match mode {
+            SavedGizmoMode::RotateView => GizmoMode::RotateView,
+            SavedGizmoMode::RotateX => GizmoMode::RotateX,
+            SavedGizmoMode::RotateY => GizmoMode::RotateY,
+            SavedGizmoMode::RotateZ => GizmoMode::RotateZ,
+            SavedGizmoMode::TranslateView => GizmoMode::TranslateView,
+            SavedGizmoMode::TranslateX => GizmoMode::TranslateX,
+            SavedGizmoMode::TranslateY => GizmoMode::TranslateY,
+            SavedGizmoMode::TranslateZ => GizmoMode::TranslateZ,
+            SavedGizmoMode::TranslateXY => GizmoMode::TranslateXY,
+            SavedGizmoMode::TranslateXZ => GizmoMode::TranslateXZ,
+            SavedGizmoMode::TranslateYZ => GizmoMode::TranslateYZ,
+            SavedGizmoMode::ScaleUniform => GizmoMode::ScaleUniform,
+            SavedGizmoMode::ScaleX => GizmoMode::ScaleX,
+            SavedGizmoMode::ScaleY => GizmoMode::ScaleY,
+            SavedGizmoMode::ScaleZ => GizmoMode::ScaleZ,
+            SavedGizmoMode::ScaleXY => GizmoMode::ScaleXY,
+            SavedGizmoMode::ScaleXZ => GizmoMode::ScaleXZ,
+            SavedGizmoMode::ScaleYZ => GizmoMode::ScaleYZ,
+            SavedGizmoMode::Arcball => GizmoMode::Arcball,
+        }
+    }
+}
+
 pub struct GizmoWindow;
 
 impl EditorWindow for GizmoWindow {
@@ -42,79 +127,18 @@ impl EditorWindow for GizmoWindow {
         // could definitely change some settings here in the future
     }
 
-    /// Called every frame (hopefully), could this invariant (namely being called every frame) be documented,
-    /// ideally in the [EditorWindow] trait?
-    fn viewport_toolbar_ui(world: &mut World, cx: EditorWindowContext, _ui: &mut egui::Ui) {
-        let gizmo_state = cx.state::<GizmoWindow>().unwrap();
-
-        // syncs the [GizmoOptions] resource with the current state of the gizmo window
-        let mut gizmo_options = world.resource_mut::<transform_gizmo_bevy::GizmoOptions>();
-        gizmo_options.gizmo_modes = gizmo_state.gizmo_modes;
-
-        if gizmo_state.camera_gizmo_active {
-            /// Before [hydrate_gizmos] and [deconstruct_gizmos] are run, this system resets the state of all entities that have a [EntityShouldShowGizmo] component.
-            /// Then, according to selection logic some entities are marked as focussed, and [hydrate_gizmos] and [deconstruct_gizmos] is run to sync the gizmo state with the selection state.
-            fn reset_gizmos_selected_state(
-                mut commands: Commands,
-                entities: Query<Entity, With<EntityShouldShowGizmo>>,
-            ) {
-                for entity in entities.iter() {
-                    commands.entity(entity).remove::<EntityShouldShowGizmo>();
-                }
-            }
-
-            /// Takes all entities marked with [EntityShouldShowGizmo] and adds the [GizmoTarget] component to them.
-            fn hydrate_gizmos(
-                mut commands: Commands,
-                entities: Query<Entity, (With<EntityShouldShowGizmo>, Without<GizmoTarget>)>,
-            ) {
-                for entity in entities.iter() {
-                    if let Some(mut entity) = commands.get_entity(entity) {
-                        trace!(
-                            "Hydrating a gizmo on entity {:?} because it is selected",
-                            entity.id()
-                        );
-                        // implicitly assumes it is the only gizmo target in the world,
-                        // otherwise setting [GizmoTarget].is_focussed may be necessary
-                        entity.insert(GizmoTarget::default());
-                    }
-                }
-            }
-
-            /// Takes all entities that should have their [GizmoTarget] removed because they are no longer selected.
-            fn deconstruct_gizmos(
-                mut commands: Commands,
-                entities: Query<Entity, (With<GizmoTarget>, Without<EntityShouldShowGizmo>)>,
-            ) {
-                for entity in entities.iter() {
-                    if let Some(mut entity) = commands.get_entity(entity) {
-                        entity.remove::<GizmoTarget>();
-                        debug!(
-                            "Removing GizmoTarget from entity {:?} because it has lost focus",
-                            entity.id()
-                        );
-                    }
-                }
-            }
-
-            if let Some(hierarchy_state) = cx.state::<HierarchyWindow>() {
-                // here should assign the `EntityShouldShowGizmo` component, which is later synced
-                // with the actual gizmo ui system
-
-                // todo: not ignore errors
-                world.run_system_once(reset_gizmos_selected_state).ok();
-
-                let selected_entities = hierarchy_state.selected.iter();
-                for entity in selected_entities {
-                    if let Ok(mut entity) = world.get_entity_mut(entity) {
-                        entity.insert(EntityShouldShowGizmo);
-                    }
-                }
-
-                world.run_system_once(hydrate_gizmos).ok();
-                world.run_system_once(deconstruct_gizmos).ok();
-            }
-        }
+    fn save_state(state: &Self::State) -> Option<String> {
+        save_ron(&SavedGizmoState {
+            camera_gizmo_active: state.camera_gizmo_active,
+            gizmo_modes: state.gizmo_modes.iter().map(SavedGizmoMode::from).collect(),
+        })
+    }
+    fn load_state(state: &mut Self::State, saved: &str) {
+        let Some(saved) = load_ron::<SavedGizmoState>(saved) else {
+            return;
+        };
+        state.camera_gizmo_active = saved.camera_gizmo_active;
+        state.gizmo_modes = saved.gizmo_modes.into_iter().map(GizmoMode::from).collect();
     }
 
     fn app_setup(app: &mut App) {
@@ -146,7 +170,15 @@ impl EditorWindow for GizmoWindow {
             camera_material: material_camera,
         });
 
-        app.add_systems(PostUpdate, add_gizmo_markers);
+        app.add_systems(Update, sync_gizmo_targets.in_set(EditorSet::Active))
+            .add_systems(
+                PostUpdate,
+                (
+                    add_gizmo_markers,
+                    set_gizmo_marker_layers,
+                    record_gizmo_drags.in_set(EditorSet::Active),
+                ),
+            );
     }
 }
 
@@ -164,10 +196,9 @@ struct GizmoMarkerConfig {
 #[derive(Component)]
 struct HasGizmoMarker;
 
-/// When on an entity, this entity should be controllable using some sort of user gizmo.
-/// Currently uses [transform_gizmo_bevy], and puts the [GizmoTarget] on the entity.
+/// The visual marker of a light or camera, rendered only by the editor cameras
 #[derive(Component)]
-struct EntityShouldShowGizmo;
+struct GizmoMarker;
 
 type GizmoMarkerQuery<'w, 's, T, F = ()> =
     Query<'w, 's, Entity, (With<T>, Without<HasGizmoMarker>, F)>;
@@ -175,6 +206,7 @@ type GizmoMarkerQuery<'w, 's, T, F = ()> =
 fn add_gizmo_markers(
     mut commands: Commands,
     gizmo_marker_meshes: Res<GizmoMarkerConfig>,
+    settings: Res<EditorSettings>,
 
     point_lights: GizmoMarkerQuery<PointLight>,
     directional_lights: GizmoMarkerQuery<DirectionalLight>,
@@ -183,29 +215,43 @@ fn add_gizmo_markers(
     fn add<T: Component, F: QueryFilter, B: Bundle>(
         commands: &mut Commands,
         query: GizmoMarkerQuery<T, F>,
+        render_layers: &RenderLayers,
         name: &'static str,
         f: impl Fn() -> B,
     ) {
-        let render_layers = RenderLayers::layer(EDITOR_RENDER_LAYER);
         for entity in &query {
             commands
                 .entity(entity)
                 .insert(HasGizmoMarker)
                 .with_children(|commands| {
-                    commands.spawn((f(), render_layers.clone(), Name::new(name)));
+                    commands.spawn((
+                        f(),
+                        GizmoMarker,
+                        NotInScene,
+                        render_layers.clone(),
+                        Name::new(name),
+                    ));
                 });
         }
     }
 
-    add(&mut commands, point_lights, "PointLight Gizmo", || {
-        (
-            Mesh3d(gizmo_marker_meshes.point_light_mesh.clone_weak()),
-            MeshMaterial3d(gizmo_marker_meshes.point_light_material.clone_weak()),
-        )
-    });
+    let render_layers = RenderLayers::layer(settings.render_layer);
+    add(
+        &mut commands,
+        point_lights,
+        &render_layers,
+        "PointLight Gizmo",
+        || {
+            (
+                Mesh3d(gizmo_marker_meshes.point_light_mesh.clone_weak()),
+                MeshMaterial3d(gizmo_marker_meshes.point_light_material.clone_weak()),
+            )
+        },
+    );
     add(
         &mut commands,
         directional_lights,
+        &render_layers,
         "DirectionalLight Gizmo",
         || {
             (
@@ -215,7 +261,6 @@ fn add_gizmo_markers(
         },
     );
 
-    let render_layers = RenderLayers::layer(EDITOR_RENDER_LAYER);
     for entity in &cameras {
         commands
             .entity(entity)
@@ -229,9 +274,101 @@ fn add_gizmo_markers(
                 commands.spawn((
                     Mesh3d(gizmo_marker_meshes.camera_mesh.clone_weak()),
                     MeshMaterial3d(gizmo_marker_meshes.camera_material.clone_weak()),
+                    GizmoMarker,
+                    NotInScene,
                     render_layers.clone(),
                     Name::new("Camera Gizmo"),
                 ));
             });
     }
 }
+
+/// Keeps the markers on the editor render layer when it is changed in the [`EditorSettings`]
+fn set_gizmo_marker_layers(
+    settings: Res<EditorSettings>,
+    mut markers: Query<&mut RenderLayers, With<GizmoMarker>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    let render_layers = RenderLayers::layer(settings.render_layer);
+    for mut layers in &mut markers {
+        if *layers != render_layers {
+            *layers = render_layers.clone();
+        }
+    }
+}
+
+/// Syncs the [`GizmoOptions`](transform_gizmo_bevy::GizmoOptions) with the gizmo window,
+/// and puts a [`GizmoTarget`] on the selected entities while removing it from the others.
+fn sync_gizmo_targets(
+    mut commands: Commands,
+    editor: Res<Editor>,
+    mut gizmo_options: ResMut<transform_gizmo_bevy::GizmoOptions>,
+    targets: Query<Entity, With<GizmoTarget>>,
+) {
+    let Some(gizmo_state) = editor.window_state::<GizmoWindow>() else {
+        return;
+    };
+    if gizmo_options.gizmo_modes != gizmo_state.gizmo_modes {
+        gizmo_options.gizmo_modes = gizmo_state.gizmo_modes;
+    }
+
+    if !gizmo_state.camera_gizmo_active {
+        return;
+    }
+    let Some(hierarchy_state) = editor.window_state::<HierarchyWindow>() else {
+        return;
+    };
+
+    for entity in hierarchy_state.selected.iter() {
+        if targets.contains(entity) {
+            continue;
+        }
+        if let Some(mut entity) = commands.get_entity(entity) {
+            trace!(
+                "Hydrating a gizmo on entity {:?} because it is selected",
+                entity.id()
+            );
+            // implicitly assumes it is the only gizmo target in the world,
+            // otherwise setting [GizmoTarget].is_focussed may be necessary
+            entity.insert(GizmoTarget::default());
+        }
+    }
+    for entity in &targets {
+        if !hierarchy_state.selected.contains(entity) {
+            commands.entity(entity).remove::<GizmoTarget>();
+            debug!(
+                "Removing GizmoTarget from entity {:?} because it has lost focus",
+                entity
+            );
+        }
+    }
+}
+
+/// Records each finished gizmo drag as a single [`Transform`] change on the [`UndoStack`].
+fn record_gizmo_drags(
+    mut drag_start: Local<EntityHashMap<(Transform, bool)>>,
+    targets: Query<(Entity, &GizmoTarget, &Transform)>,
+    mut undo_stack: ResMut<UndoStack>,
+) {
+    drag_start.retain(|entity, _| targets.contains(*entity));
+
+    for (entity, target, &transform) in &targets {
+        let (before, was_active) = drag_start.entry(entity).or_insert((transform, false));
+        if target.is_active() {
+            *was_active = true;
+            continue;
+        }
+
+        if *was_active && *before != transform {
+            undo_stack.push(ReflectComponentChange::new(
+                entity,
+                Some(*before),
+                Some(transform),
+            ));
+        }
+        *before = transform;
+        *was_active = false;
+    }
+}
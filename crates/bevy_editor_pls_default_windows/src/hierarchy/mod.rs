@@ -12,7 +12,8 @@ use bevy_inspector_egui::egui::{self, ScrollArea};
 
 use bevy_editor_pls_core::{
     editor_window::{EditorWindow, EditorWindowContext},
-    Editor,
+    undo::{self, DespawnEntities, ReflectComponentChange, UndoStack},
+    AddStatusBarItem, Editor, EditorEvent,
 };
 // use bevy_mod_picking::backends::egui::EguiPointer;
 // use bevy_mod_picking::prelude::{IsPointerEvent, PointerClick, PointerButton};
@@ -32,13 +33,14 @@ impl EditorWindow for HierarchyWindow {
     fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
         let (hierarchy_state, inspector_state, add_state) =
             match cx.state_mut_triplet::<HierarchyWindow, InspectorWindow, AddWindow>() {
-                Some((a, b, c)) => (a, b, Some(c)),
-                None => {
-                    let (a, b) = cx
-                        .state_mut_pair::<HierarchyWindow, InspectorWindow>()
-                        .unwrap();
-                    (a, b, None)
-                }
+                Some((a, b, c)) => (a, Some(b), Some(c)),
+                None => match cx.state_mut_pair::<HierarchyWindow, InspectorWindow>() {
+                    Some((a, b)) => (a, Some(b), None),
+                    None => match cx.state_mut_pair::<HierarchyWindow, AddWindow>() {
+                        Some((a, c)) => (a, None, Some(c)),
+                        None => (cx.state_mut::<HierarchyWindow>().unwrap(), None, None),
+                    },
+                },
             };
 
         ScrollArea::vertical().show(ui, |ui| {
@@ -52,7 +54,7 @@ impl EditorWindow for HierarchyWindow {
             }
             .show(ui);
 
-            if new_selected {
+            if let Some(inspector_state) = inspector_state.filter(|_| new_selected) {
                 inspector_state.selected = InspectorSelection::Entities;
             }
         });
@@ -60,11 +62,17 @@ impl EditorWindow for HierarchyWindow {
 
     fn app_setup(app: &mut bevy::prelude::App) {
         // picking::setup(app);
-        app.add_systems(PostUpdate, clear_removed_entites);
+        app.add_systems(
+            PostUpdate,
+            (clear_removed_entites, send_selection_changed).chain(),
+        )
+        .add_systems(Update, handle_select_events)
+        .add_status_bar_item(0, selection_status_ui);
         // .add_system(handle_events);
 
-        app.sub_app_mut(RenderApp)
-            .add_systems(ExtractSchedule, extract_wireframe_for_selected);
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(ExtractSchedule, extract_wireframe_for_selected);
+        }
     }
 }
 
@@ -73,6 +81,49 @@ fn clear_removed_entites(mut editor: ResMut<Editor>, entities: &Entities) {
     state.selected.retain(|entity| entities.contains(entity));
 }
 
+fn send_selection_changed(
+    editor: Res<Editor>,
+    mut previous: Local<Vec<Entity>>,
+    mut editor_events: EventWriter<EditorEvent>,
+) {
+    let Some(state) = editor.window_state::<HierarchyWindow>() else {
+        return;
+    };
+    if state.selected.iter().eq(previous.iter().copied()) {
+        return;
+    }
+
+    *previous = state.selected.iter().collect();
+    editor_events.send(EditorEvent::SelectionChanged {
+        selected: previous.clone(),
+    });
+}
+
+fn handle_select_events(mut editor_events: EventReader<EditorEvent>, mut editor: ResMut<Editor>) {
+    for event in editor_events.read() {
+        let EditorEvent::SelectEntity(entity) = *event else {
+            continue;
+        };
+
+        let state = editor.window_state_mut::<HierarchyWindow>().unwrap();
+        state.selected.select_replace(entity);
+        if let Some(inspector_state) = editor.window_state_mut::<InspectorWindow>() {
+            inspector_state.selected = InspectorSelection::Entities;
+        }
+    }
+}
+
+fn selection_status_ui(editor: &Editor, _: &mut World, ui: &mut egui::Ui) {
+    let Some(state) = editor.window_state::<HierarchyWindow>() else {
+        return;
+    };
+    match state.selected.len() {
+        0 => ui.label("No selection"),
+        1 => ui.label("1 entity selected"),
+        len => ui.label(format!("{len} entities selected")),
+    };
+}
+
 /*fn handle_events(
     mut click_events: EventReader<PointerClick>,
     mut editor: ResMut<Editor>,
@@ -111,7 +162,7 @@ fn clear_removed_entites(mut editor: ResMut<Editor>, entities: &Entities) {
 fn extract_wireframe_for_selected(editor: Extract<Res<Editor>>, mut commands: Commands) {
     let wireframe_for_selected = editor
         .window_state::<DebugSettingsWindow>()
-        .map_or(false, |settings| settings.highlight_selected);
+        .is_some_and(|settings| settings.highlight_selected);
 
     if wireframe_for_selected {
         let selected = &editor.window_state::<HierarchyWindow>().unwrap().selected;
@@ -179,19 +230,22 @@ impl Hierarchy<'_> {
                 if let Some(add_state) = self.add_state {
                     ui.menu_button("Add", |ui| {
                         if let Some(add_item) = add_ui(ui, add_state) {
-                            add_item.add_to_entity(world, entity);
+                            undo::track_edits(world, &[entity], |world| {
+                                add_item.add_to_entity(world, entity)
+                            });
                             ui.close_menu();
                         }
                     });
                 }
             }),
             shortcircuit_entity: Some(&mut |ui, entity, world, rename_info| {
-                if let Some(rename_info) = rename_info {
-                    if rename_info.renaming && rename_info.entity == entity {
-                        rename_entity_ui(ui, rename_info, world);
+                if let Some(rename_info) = rename_info
+                    && rename_info.renaming
+                    && rename_info.entity == entity
+                {
+                    rename_entity_ui(ui, rename_info, world);
 
-                        return true;
-                    }
+                    return true;
                 }
 
                 false
@@ -200,17 +254,20 @@ impl Hierarchy<'_> {
         .show::<Without<HideInEditor>>(ui);
 
         if let Some(entity) = despawn_recursive {
-            bevy::hierarchy::despawn_with_children_recursive(self.world, entity, true);
+            let command = DespawnEntities::execute(self.world, &[entity], true);
+            self.world.resource_mut::<UndoStack>().push(command);
         }
         if let Some(entity) = despawn {
-            self.world.entity_mut(entity).despawn();
+            let command = DespawnEntities::execute(self.world, &[entity], false);
+            self.world.resource_mut::<UndoStack>().push(command);
             self.state.selected.remove(entity);
         }
 
-        if ui.input(|input| input.key_pressed(egui::Key::Delete)) {
-            for entity in self.state.selected.iter() {
-                self.world.entity_mut(entity).despawn_recursive();
-            }
+        if ui.input(|input| input.key_pressed(egui::Key::Delete)) && !self.state.selected.is_empty()
+        {
+            let selected: Vec<_> = self.state.selected.iter().collect();
+            let command = DespawnEntities::execute(self.world, &selected, true);
+            self.world.resource_mut::<UndoStack>().push(command);
             self.state.selected.clear();
         }
 
@@ -236,14 +293,29 @@ fn rename_entity_ui(ui: &mut egui::Ui, rename_info: &mut RenameInfo, world: &mut
         rename_info.renaming = false;
 
         match world.get_entity_mut(rename_info.entity) {
-            Ok(mut ent_mut) => match ent_mut.get_mut::<Name>() {
-                Some(mut name) => {
-                    name.set(rename_info.current_rename.clone());
+            Ok(mut ent_mut) => {
+                let before = ent_mut.get::<Name>().cloned();
+                let after = Name::new(rename_info.current_rename.clone());
+                match ent_mut.get_mut::<Name>() {
+                    Some(mut name) => {
+                        name.set(rename_info.current_rename.clone());
+                    }
+                    None => {
+                        ent_mut.insert(after.clone());
+                    }
                 }
-                None => {
-                    ent_mut.insert(Name::new(rename_info.current_rename.clone()));
+                if before.as_ref() != Some(&after) {
+                    world.send_event(EditorEvent::EntityRenamed {
+                        entity: rename_info.entity,
+                        name: after.clone(),
+                    });
+                    world.resource_mut::<UndoStack>().push(ReflectComponentChange::new(
+                        rename_info.entity,
+                        before,
+                        Some(after),
+                    ));
                 }
-            },
+            }
             Err(err) => {
                 error!(?err, "Failed to get renamed entity");
             }
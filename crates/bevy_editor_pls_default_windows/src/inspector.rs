@@ -6,6 +6,7 @@ use bevy::asset::UntypedAssetId;
 use bevy::prelude::{AppTypeRegistry, Entity, World};
 use bevy::reflect::TypeRegistry;
 use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
+use bevy_editor_pls_core::undo::{ComponentEditTracker, UndoStack};
 use bevy_inspector_egui::bevy_inspector::hierarchy::SelectedEntities;
 use bevy_inspector_egui::{bevy_inspector, egui};
 
@@ -18,12 +19,17 @@ pub enum InspectorSelection {
 
 pub struct InspectorState {
     pub selected: InspectorSelection,
+    /// Entities shown instead of the hierarchy selection, so that several inspectors can show different entities
+    pub locked: Option<Vec<Entity>>,
+    edit_tracker: ComponentEditTracker,
 }
 
 impl Default for InspectorState {
     fn default() -> Self {
         Self {
             selected: InspectorSelection::Entities,
+            locked: None,
+            edit_tracker: ComponentEditTracker::default(),
         }
     }
 }
@@ -32,46 +38,93 @@ pub struct InspectorWindow;
 impl EditorWindow for InspectorWindow {
     type State = InspectorState;
     const NAME: &'static str = "Inspector";
+    const MULTIPLE_INSTANCES: bool = true;
 
-    fn ui(world: &mut World, cx: EditorWindowContext, ui: &mut egui::Ui) {
+    fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
         let type_registry = world.resource::<AppTypeRegistry>().0.clone();
         let type_registry = type_registry.read();
 
-        let selected = &cx.state::<Self>().unwrap().selected;
-        let selected_entities = &cx.state::<HierarchyWindow>().unwrap().selected;
+        // without the hierarchy window nothing can be selected, but resources and assets can still be inspected
+        let no_selection = SelectedEntities::default();
+        let (state, selected, add_window_state) =
+            match cx.state_mut_triplet::<InspectorWindow, HierarchyWindow, AddWindow>() {
+                Some((a, b, c)) => (a, &b.selected, Some(c)),
+                None => match cx.state_mut_pair::<InspectorWindow, HierarchyWindow>() {
+                    Some((a, b)) => (a, &b.selected, None),
+                    None => match cx.state_mut_pair::<InspectorWindow, AddWindow>() {
+                        Some((a, c)) => (a, &no_selection, Some(c)),
+                        None => (
+                            cx.state_mut::<InspectorWindow>().unwrap(),
+                            &no_selection,
+                            None,
+                        ),
+                    },
+                },
+            };
 
-        let add_window_state = cx.state::<AddWindow>();
         inspector(
             world,
+            state,
             selected,
-            selected_entities,
             ui,
-            add_window_state,
+            add_window_state.as_deref(),
             &type_registry,
         );
+
+        // a drag or text edit is recorded as a single undo step once it ends
+        let interacting = ui.ctx().input(|input| input.pointer.any_down())
+            || ui.ctx().wants_keyboard_input();
+        if !interacting && state.edit_tracker.has_pending() {
+            state
+                .edit_tracker
+                .finish(&mut world.resource_mut::<UndoStack>());
+        }
     }
 }
 
 fn inspector(
     world: &mut World,
-    selected: &InspectorSelection,
+    state: &mut InspectorState,
     selected_entities: &SelectedEntities,
     ui: &mut egui::Ui,
     add_window_state: Option<&AddWindowState>,
     type_registry: &TypeRegistry,
 ) {
-    egui::ScrollArea::vertical().show(ui, |ui| match *selected {
-        InspectorSelection::Entities => match selected_entities.as_slice() {
+    if state.selected == InspectorSelection::Entities {
+        let mut locked = state.locked.is_some();
+        if ui
+            .toggle_value(&mut locked, "🔒 Lock")
+            .on_hover_text("Keep showing the current selection")
+            .changed()
+        {
+            state.locked = locked.then(|| selected_entities.as_slice().to_vec());
+        }
+    }
+    if let Some(locked) = &mut state.locked {
+        locked.retain(|&entity| world.get_entity(entity).is_ok());
+    }
+
+    let entities = state
+        .locked
+        .as_deref()
+        .unwrap_or(selected_entities.as_slice());
+    let edit_tracker = &mut state.edit_tracker;
+    egui::ScrollArea::vertical().show(ui, |ui| match state.selected {
+        InspectorSelection::Entities => match entities {
             [] => {
                 ui.label("No entity selected");
             }
             &[entity] => {
-                bevy_inspector::ui_for_entity(world, entity, ui);
-                add_ui(ui, &[entity], world, add_window_state);
+                edit_tracker.track(world, &[entity], |world| {
+                    bevy_inspector::ui_for_entity(world, entity, ui);
+                    add_ui(ui, &[entity], world, add_window_state);
+                });
             }
             entities => {
-                bevy_inspector::ui_for_entities_shared_components(world, entities, ui);
-                add_ui(ui, entities, world, add_window_state);
+                edit_tracker.track(world, entities, |world| {
+                    bevy_inspector::ui_for_entities_shared_components(world, entities, ui);
+                    add_ui(ui, entities, world, add_window_state);
+                });
             }
         },
         InspectorSelection::Resource(type_id, ref name) => {
@@ -9,6 +9,10 @@ pub mod diagnostics;
 pub mod gizmos;
 pub mod hierarchy;
 pub mod inspector;
+pub mod play_mode;
+pub mod preferences;
 pub mod renderer;
 pub mod resources;
 pub mod scenes;
+pub mod time_control;
+pub mod viewport_overlay;
@@ -0,0 +1,261 @@
+//! Optional "edit mode vs play mode" workflow: the scene is snapshotted when the editor is toggled off,
+//! and restored when it is toggled back on.
+
+use bevy::ecs::entity::{EntityHashMap, EntityHashSet};
+use bevy::ecs::observer::Observer;
+use bevy::prelude::*;
+use bevy::scene::{DynamicEntity, SceneFilter, SceneSpawnError};
+use bevy::window::Monitor;
+use bevy_editor_pls_core::{Editor, EditorEvent, Notifications};
+
+use crate::hierarchy::HierarchyWindow;
+use crate::scenes::NotInScene;
+
+/// Entities which are part of the play mode snapshot
+type SceneEntityFilter = (
+    Without<NotInScene>,
+    Without<Window>,
+    Without<Monitor>,
+    Without<Observer>,
+);
+
+/// Restores the edited scene after playing.
+///
+/// When enabled, leaving the editor with [`EditorEvent::Toggle`] snapshots all entities without [`NotInScene`] and the
+/// resources chosen with [`PlayMode::snapshot_resource`] into a [`DynamicScene`].
+/// Toggling the editor back on despawns the entities spawned while playing and restores the snapshot, keeping the
+/// entity ids and the selection where possible.
+///
+/// Only reflected components and resources registered in the [`AppTypeRegistry`] are restored.
+/// Helper entities which should survive the restore, like ones spawned by plugins, should be marked [`NotInScene`].
+#[derive(Resource)]
+pub struct PlayMode {
+    pub enabled: bool,
+    resource_filter: SceneFilter,
+    snapshot: Option<DynamicScene>,
+}
+
+impl Default for PlayMode {
+    fn default() -> Self {
+        PlayMode {
+            enabled: false,
+            resource_filter: SceneFilter::deny_all(),
+            snapshot: None,
+        }
+    }
+}
+
+impl PlayMode {
+    /// Play mode which snapshots no resources
+    pub fn new() -> Self {
+        PlayMode {
+            enabled: true,
+            ..default()
+        }
+    }
+
+    /// Also snapshot and restore the resource `R`, which needs to be registered with `#[reflect(Resource)]`
+    pub fn snapshot_resource<R: Resource>(mut self) -> Self {
+        self.resource_filter = std::mem::take(&mut self.resource_filter).allow::<R>();
+        self
+    }
+
+    /// Whether there is a snapshot which gets restored when the editor is toggled on again
+    pub fn is_playing(&self) -> bool {
+        self.snapshot.is_some()
+    }
+}
+
+/// Snapshots and restores the scene on [`EditorEvent::Toggle`] while [`PlayMode`] is enabled.
+///
+/// Added by the `EditorPlugin`, independently of the `Scenes` window which only toggles it.
+pub struct PlayModePlugin;
+
+impl Plugin for PlayModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlayMode>()
+            .add_systems(Last, handle_toggle_events);
+    }
+}
+
+fn handle_toggle_events(
+    mut commands: Commands,
+    mut editor_events: EventReader<EditorEvent>,
+    play_mode: Res<PlayMode>,
+) {
+    for event in editor_events.read() {
+        let EditorEvent::Toggle { now_active } = *event else {
+            continue;
+        };
+        if !play_mode.enabled {
+            continue;
+        }
+
+        if now_active {
+            commands.queue(stop_playing);
+        } else {
+            commands.queue(start_playing);
+        }
+    }
+}
+
+/// Takes the snapshot of the scene, unless there already is one
+fn start_playing(world: &mut World) {
+    if world.resource::<PlayMode>().is_playing() {
+        return;
+    }
+
+    let mut query = world.query_filtered::<Entity, SceneEntityFilter>();
+    let entities: Vec<Entity> = query.iter(world).collect();
+
+    let resource_filter = world.resource::<PlayMode>().resource_filter.clone();
+    let snapshot = DynamicSceneBuilder::from_world(world)
+        .with_resource_filter(resource_filter)
+        .extract_entities(entities.into_iter())
+        .extract_resources()
+        .build();
+
+    world.resource_mut::<PlayMode>().snapshot = Some(snapshot);
+}
+
+/// Restores the snapshot and maps the selection to the restored entities
+fn stop_playing(world: &mut World) {
+    let Some(snapshot) = world.resource_mut::<PlayMode>().snapshot.take() else {
+        return;
+    };
+
+    let entity_map = match restore_snapshot(world, &snapshot) {
+        Ok(entity_map) => entity_map,
+        Err(error) => {
+            world.resource_mut::<Notifications>().error(format!(
+                "Could not restore the scene after playing: {error}"
+            ));
+            return;
+        }
+    };
+
+    let mut editor = world.resource_mut::<Editor>();
+    if let Some(hierarchy) = editor.window_state_mut::<HierarchyWindow>() {
+        let selected: Vec<Entity> = hierarchy.selected.iter().collect();
+        hierarchy.selected.clear();
+        for entity in selected {
+            if let Some(&entity) = entity_map.get(&entity) {
+                hierarchy.selected.select_maybe_add(entity, true);
+            }
+        }
+    }
+}
+
+/// Writes `snapshot` back into the world.
+///
+/// Entities of the snapshot which are still alive keep their id, and only components which changed while playing are
+/// replaced, so that change detection doesn't trigger for untouched ones.
+/// Returns the mapping of every live entity, including the respawned ones, to its entity after restoring.
+fn restore_snapshot(
+    world: &mut World,
+    snapshot: &DynamicScene,
+) -> Result<EntityHashMap<Entity>, SceneSpawnError> {
+    let snapshot_entities: EntityHashSet = snapshot.entities.iter().map(|e| e.entity).collect();
+
+    let mut query = world.query_filtered::<Entity, SceneEntityFilter>();
+    let spawned_while_playing: Vec<Entity> = query
+        .iter(world)
+        .filter(|entity| !snapshot_entities.contains(entity))
+        .collect();
+    for entity in spawned_while_playing {
+        if let Ok(entity) = world.get_entity_mut(entity) {
+            entity.despawn_recursive();
+        }
+    }
+
+    // references to entities outside of the snapshot, like children marked `NotInScene`, stay as they are
+    let mut entity_map: EntityHashMap<Entity> = world
+        .iter_entities()
+        .map(|entity| (entity.id(), entity.id()))
+        .collect();
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let changes = {
+        let type_registry = type_registry.read();
+
+        let mut changes = DynamicScene {
+            resources: Vec::new(),
+            entities: Vec::new(),
+        };
+
+        for resource in &snapshot.resources {
+            // lists in resources are only extended when applied, so replace them completely
+            if let Some(reflect_resource) = resource
+                .get_represented_type_info()
+                .and_then(|info| type_registry.get_type_data::<ReflectResource>(info.type_id()))
+            {
+                reflect_resource.remove(world);
+            }
+            changes.resources.push(resource.clone_value());
+        }
+
+        for snapshot_entity in &snapshot.entities {
+            let Ok(mut entity) = world.get_entity_mut(snapshot_entity.entity) else {
+                // despawned while playing, gets respawned
+                changes.entities.push(DynamicEntity {
+                    entity: snapshot_entity.entity,
+                    components: snapshot_entity
+                        .components
+                        .iter()
+                        .map(|component| component.clone_value())
+                        .collect(),
+                });
+                continue;
+            };
+
+            let component_types: Vec<_> = entity
+                .archetype()
+                .components()
+                .filter_map(|id| entity.world().components().get_info(id)?.type_id())
+                .collect();
+
+            let mut components = Vec::new();
+            for &type_id in &component_types {
+                let Some(reflect_component) =
+                    type_registry.get_type_data::<ReflectComponent>(type_id)
+                else {
+                    continue;
+                };
+                let saved = snapshot_entity.components.iter().find(|component| {
+                    component
+                        .get_represented_type_info()
+                        .is_some_and(|info| info.type_id() == type_id)
+                });
+
+                let unchanged = saved.is_some_and(|saved| {
+                    reflect_component
+                        .reflect(EntityRef::from(&entity))
+                        .and_then(|current| current.reflect_partial_eq(saved.as_ref()))
+                        .unwrap_or(false)
+                });
+                if !unchanged {
+                    reflect_component.remove(&mut entity);
+                    components.extend(saved.map(|saved| saved.clone_value()));
+                }
+            }
+
+            // components removed while playing
+            for saved in &snapshot_entity.components {
+                let type_id = saved.get_represented_type_info().map(|info| info.type_id());
+                if !type_id.is_some_and(|type_id| component_types.contains(&type_id)) {
+                    components.push(saved.clone_value());
+                }
+            }
+
+            changes.entities.push(DynamicEntity {
+                entity: snapshot_entity.entity,
+                components,
+            });
+        }
+
+        changes
+    };
+
+    changes.write_to_world(world, &mut entity_map)?;
+    Ok(entity_map)
+}
@@ -0,0 +1,215 @@
+use bevy::prelude::*;
+use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
+use bevy_editor_pls_core::theme::{EditorTheme, ThemePreset};
+use bevy_editor_pls_core::{EditorPersistence, EditorSettings, Notifications};
+use bevy_inspector_egui::egui;
+
+const UI_SCALES: [f32; 7] = [0.75, 1.0, 1.25, 1.5, 1.75, 2.0, 2.5];
+
+pub struct PreferencesWindow;
+
+impl EditorWindow for PreferencesWindow {
+    type State = ();
+    const NAME: &'static str = "Preferences";
+    const DEFAULT_SIZE: (f32, f32) = (320.0, 400.0);
+
+    fn ui(world: &mut World, _cx: EditorWindowContext, ui: &mut egui::Ui) {
+        // edit copies, so that change detection only triggers when something changed
+        let mut theme = world.resource::<EditorTheme>().clone();
+        ui.heading("Theme");
+        egui::Grid::new("theme preferences")
+            .num_columns(2)
+            .show(ui, |ui| theme_ui(&mut theme, ui));
+        if theme != *world.resource::<EditorTheme>() {
+            world.insert_resource(theme);
+        }
+
+        let mut settings = world.resource::<EditorSettings>().clone();
+        ui.heading("Editor");
+        egui::Grid::new("editor settings")
+            .num_columns(2)
+            .show(ui, |ui| settings_ui(&mut settings, ui));
+        if settings != *world.resource::<EditorSettings>() {
+            world.insert_resource(settings);
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui.button("Reset").clicked() {
+                world.insert_resource(EditorTheme::default());
+                world.insert_resource(EditorSettings::default());
+            }
+            if ui.button("Save").clicked() {
+                save_preferences(world);
+            }
+            if ui.button("Load").clicked() {
+                load_preferences(world);
+            }
+        });
+    }
+}
+
+fn theme_ui(theme: &mut EditorTheme, ui: &mut egui::Ui) {
+    ui.label("Theme");
+    egui::ComboBox::from_id_salt("theme preset")
+        .selected_text(theme.preset.name())
+        .show_ui(ui, |ui| {
+            for preset in ThemePreset::all() {
+                ui.selectable_value(&mut theme.preset, preset, preset.name());
+            }
+        });
+    ui.end_row();
+
+    ui.label("Accent color");
+    ui.horizontal(|ui| {
+        let mut custom_accent = theme.accent.is_some();
+        if ui.checkbox(&mut custom_accent, "").changed() {
+            theme.accent = custom_accent.then(|| theme.visuals().selection.bg_fill);
+        }
+        if let Some(accent) = &mut theme.accent {
+            ui.color_edit_button_srgba(accent);
+        }
+    });
+    ui.end_row();
+
+    ui.label("Font size");
+    ui.add(
+        egui::DragValue::new(&mut theme.font_size)
+            .range(8.0..=32.0)
+            .speed(0.1)
+            .suffix(" pt"),
+    );
+    ui.end_row();
+
+    ui.label("UI scale");
+    egui::ComboBox::from_id_salt("ui scale")
+        .selected_text(format!("{:.0}%", theme.ui_scale * 100.0))
+        .show_ui(ui, |ui| {
+            for scale in UI_SCALES {
+                let text = format!("{:.0}%", scale * 100.0);
+                ui.selectable_value(&mut theme.ui_scale, scale, text);
+            }
+        });
+    ui.end_row();
+}
+
+fn settings_ui(settings: &mut EditorSettings, ui: &mut egui::Ui) {
+    ui.label("Render layer")
+        .on_hover_text("Render layer of the editor cameras, which no game camera should use");
+    ui.add(egui::DragValue::new(&mut settings.render_layer).range(1..=63));
+    ui.end_row();
+
+    ui.label("Camera order").on_hover_text(
+        "Order of the editor cameras, should be above the order of all game cameras",
+    );
+    ui.add(egui::DragValue::new(&mut settings.camera_order));
+    ui.end_row();
+
+    ui.label("Focus distance");
+    ui.add(
+        egui::DragValue::new(&mut settings.focus_radius_multiplier)
+            .range(0.1..=20.0)
+            .speed(0.05)
+            .suffix("x"),
+    );
+    ui.end_row();
+
+    ui.label("Default scene file");
+    egui::TextEdit::singleline(&mut settings.default_scene_filename)
+        .desired_width(150.0)
+        .show(ui);
+    ui.end_row();
+
+    ui.label("Look sensitivity");
+    ui.add(
+        egui::DragValue::new(&mut settings.look_sensitivity)
+            .range(0.05..=10.0)
+            .speed(0.01),
+    );
+    ui.end_row();
+
+    ui.label("Zoom sensitivity");
+    ui.add(
+        egui::DragValue::new(&mut settings.zoom_sensitivity)
+            .range(0.05..=10.0)
+            .speed(0.01),
+    );
+    ui.end_row();
+
+    ui.label("Fly speed");
+    ui.add(
+        egui::DragValue::new(&mut settings.fly_speed)
+            .range(0.1..=1000.0)
+            .speed(0.1),
+    );
+    ui.end_row();
+
+    ui.label("Fly boost speed");
+    ui.add(
+        egui::DragValue::new(&mut settings.fly_boost_speed)
+            .range(0.1..=1000.0)
+            .speed(0.1),
+    );
+    ui.end_row();
+}
+
+fn save_preferences(world: &mut World) {
+    let persistence = world.resource::<EditorPersistence>();
+    let (Some(theme_path), Some(settings_path)) = (
+        persistence.user_path(EditorPersistence::THEME_FILE),
+        persistence.user_path(EditorPersistence::SETTINGS_FILE),
+    ) else {
+        world
+            .resource_mut::<Notifications>()
+            .warning("Editor persistence is disabled, the preferences can't be saved");
+        return;
+    };
+
+    let result = world
+        .resource::<EditorTheme>()
+        .save(&theme_path)
+        .map_err(|error| (theme_path, error))
+        .and_then(|()| {
+            world
+                .resource::<EditorSettings>()
+                .save(&settings_path)
+                .map_err(|error| (settings_path, error))
+        });
+    let mut notifications = world.resource_mut::<Notifications>();
+    match result {
+        Ok(()) => notifications.success("Saved preferences"),
+        Err((path, error)) => notifications.error(format!(
+            "Could not save preferences to {}: {error}",
+            path.display()
+        )),
+    }
+}
+
+/// Reloads the theme and settings files, e.g. after editing them by hand
+fn load_preferences(world: &mut World) {
+    let persistence = world.resource::<EditorPersistence>();
+    let (Some(theme_path), Some(settings_path)) = (
+        persistence.user_path(EditorPersistence::THEME_FILE),
+        persistence.user_path(EditorPersistence::SETTINGS_FILE),
+    ) else {
+        world
+            .resource_mut::<Notifications>()
+            .warning("Editor persistence is disabled, there are no preferences to load");
+        return;
+    };
+
+    match EditorTheme::load(&theme_path) {
+        Ok(theme) => world.insert_resource(theme),
+        Err(error) => world.resource_mut::<Notifications>().error(format!(
+            "Could not load theme from {}: {error}",
+            theme_path.display()
+        )),
+    }
+    match EditorSettings::load(&settings_path) {
+        Ok(settings) => world.insert_resource(settings),
+        Err(error) => world.resource_mut::<Notifications>().error(format!(
+            "Could not load editor settings from {}: {error}",
+            settings_path.display()
+        )),
+    }
+}
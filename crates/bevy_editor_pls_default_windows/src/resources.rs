@@ -15,7 +15,11 @@ impl EditorWindow for ResourcesWindow {
     const NAME: &'static str = "Resources";
 
     fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
-        let selection = &mut cx.state_mut::<InspectorWindow>().unwrap().selected;
+        let Some(inspector_state) = cx.state_mut::<InspectorWindow>() else {
+            ui.label("Selecting resources requires the Inspector window");
+            return;
+        };
+        let selection = &mut inspector_state.selected;
         let type_registry = world.resource::<AppTypeRegistry>();
         let type_registry = type_registry.read();
 
@@ -38,7 +42,7 @@ fn select_resource(
             )
         })
         .collect();
-    resources.sort_by(|(name_a, _), (name_b, _)| name_a.cmp(name_b));
+    resources.sort_by_key(|(name, _)| *name);
 
     for (resource_name, type_id) in resources {
         let selected = match *selection {
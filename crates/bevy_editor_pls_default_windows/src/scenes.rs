@@ -1,16 +1,20 @@
 use bevy::prelude::*;
-use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
-use bevy_inspector_egui::egui::{self, RichText};
+use bevy_editor_pls_core::editor_window::{load_ron, save_ron, EditorWindow, EditorWindowContext};
+use bevy_editor_pls_core::menu::MenuItem;
+use bevy_editor_pls_core::{
+    AddMenuItem, AddPaletteCommand, Editor, EditorEvent, EditorSettings, Notifications,
+};
+use bevy_inspector_egui::egui;
+use serde::{Deserialize, Serialize};
 
-const DEFAULT_FILENAME: &str = "scene.scn.ron";
+use crate::play_mode::PlayMode;
 
 #[derive(Default, Component)]
 pub struct NotInScene;
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct SceneWindowState {
     filename: String,
-    scene_save_result: Option<Result<(), Box<dyn std::error::Error + Send + Sync>>>,
 }
 
 pub struct SceneWindow;
@@ -21,41 +25,92 @@ impl EditorWindow for SceneWindow {
 
     fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
         let state = cx.state_mut::<SceneWindow>().unwrap();
+        let default_filename = world
+            .resource::<EditorSettings>()
+            .default_scene_filename
+            .clone();
 
         ui.horizontal(|ui| {
-            let res = egui::TextEdit::singleline(&mut state.filename)
-                .hint_text(DEFAULT_FILENAME)
+            egui::TextEdit::singleline(&mut state.filename)
+                .hint_text(&default_filename)
                 .desired_width(120.0)
                 .show(ui);
 
-            if res.response.changed() {
-                state.scene_save_result = None;
-            }
-
             let enter_pressed = ui.input(|input| input.key_pressed(egui::Key::Enter));
 
             if ui.button("Save").clicked() || enter_pressed {
-                let filename = if state.filename.is_empty() {
-                    DEFAULT_FILENAME
-                } else {
-                    &state.filename
-                };
-                let mut query = world.query_filtered::<Entity, Without<NotInScene>>();
-                let entitys = query.iter(world).collect();
-                state.scene_save_result = Some(save_world(world, filename, entitys));
+                save_scene(world, state.filename(&default_filename));
             }
         });
 
-        if let Some(status) = &state.scene_save_result {
-            match status {
-                Ok(()) => {
-                    ui.label(RichText::new("Success!").color(egui::Color32::GREEN));
-                }
-                Err(error) => {
-                    ui.label(RichText::new(error.to_string()).color(egui::Color32::RED));
-                }
-            }
+        let Some(mut play_mode) = world.get_resource_mut::<PlayMode>() else {
+            return;
+        };
+        ui.add_enabled(
+            !play_mode.is_playing(),
+            egui::Checkbox::new(&mut play_mode.enabled, "Restore scene after playing"),
+        )
+        .on_hover_text(
+            "Snapshot the scene when leaving the editor, and restore it when coming back",
+        );
+    }
+
+    fn save_state(state: &Self::State) -> Option<String> {
+        save_ron(state)
+    }
+    fn load_state(state: &mut Self::State, saved: &str) {
+        if let Some(saved) = load_ron(saved) {
+            *state = saved;
+        }
+    }
+
+    fn app_setup(app: &mut App) {
+        app.add_palette_command("Save scene", save_scene_command)
+            .add_menu_item("File", MenuItem::new("Save scene", save_scene_command));
+    }
+}
+
+impl SceneWindowState {
+    fn filename<'a>(&'a self, default_filename: &'a str) -> &'a str {
+        if self.filename.is_empty() {
+            default_filename
+        } else {
+            &self.filename
+        }
+    }
+}
+
+fn current_filename(world: &World) -> String {
+    let default_filename = &world.resource::<EditorSettings>().default_scene_filename;
+    world
+        .resource::<Editor>()
+        .window_state::<SceneWindow>()
+        .map_or(default_filename.as_str(), |state| {
+            state.filename(default_filename)
+        })
+        .to_owned()
+}
+
+fn save_scene_command(world: &mut World) {
+    let filename = current_filename(world);
+    save_scene(world, &filename);
+}
+
+/// Saves all entities without [`NotInScene`] to `filename` and reports the result as a notification
+fn save_scene(world: &mut World, filename: &str) {
+    let mut query = world.query_filtered::<Entity, Without<NotInScene>>();
+    let entities = query.iter(world).collect();
+
+    let result = save_world(world, filename, entities);
+    let mut notifications = world.resource_mut::<Notifications>();
+    match result {
+        Ok(()) => {
+            notifications.success(format!("Saved scene to {filename}"));
+            world.send_event(EditorEvent::SceneSaved {
+                path: filename.into(),
+            });
         }
+        Err(error) => notifications.error(format!("Could not save scene to {filename}: {error}")),
     }
 }
 
@@ -0,0 +1,238 @@
+use bevy::app::FixedMain;
+use bevy::prelude::*;
+use bevy::time::TimeSystem;
+use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
+use bevy_inspector_egui::egui;
+
+/// Relative speeds of [`Time<Virtual>`] offered in the toolbar, and cycled through by [`faster`] and [`slower`]
+pub const SPEED_PRESETS: [f64; 8] = [0.1, 0.25, 0.5, 1.0, 1.5, 2.0, 4.0, 8.0];
+
+/// Requests to advance the game frame by frame.
+///
+/// Stepping unpauses [`Time<Virtual>`] for the requested number of frames and pauses it again afterwards.
+/// Fixed update steps run [`FixedMain`] once with the timestep of [`Time<Fixed>`], without advancing virtual time.
+#[derive(Resource)]
+pub struct TimeControl {
+    /// Number of frames advanced by the `Step N frames` button and action
+    pub step_count: u32,
+    pending_frames: u32,
+    stepping: bool,
+    pending_fixed_updates: u32,
+}
+
+impl Default for TimeControl {
+    fn default() -> Self {
+        TimeControl {
+            step_count: 10,
+            pending_frames: 0,
+            stepping: false,
+            pending_fixed_updates: 0,
+        }
+    }
+}
+
+impl TimeControl {
+    /// Advances the game by `frames` frames, and pauses it afterwards
+    pub fn step_frames(&mut self, frames: u32) {
+        self.pending_frames += frames;
+    }
+
+    /// Runs the [`FixedUpdate`] schedules once in the next frame
+    pub fn step_fixed_update(&mut self) {
+        self.pending_fixed_updates += 1;
+    }
+
+    /// Whether stepped frames are still running
+    pub fn is_stepping(&self) -> bool {
+        self.pending_frames > 0 || self.stepping
+    }
+}
+
+pub fn toggle_pause(time: &mut Time<Virtual>) {
+    if time.is_paused() {
+        time.unpause();
+    } else {
+        time.pause();
+    }
+}
+
+/// The next faster preset of [`SPEED_PRESETS`]
+pub fn faster(speed: f64) -> f64 {
+    SPEED_PRESETS
+        .into_iter()
+        .find(|&preset| preset > speed)
+        .unwrap_or(speed)
+}
+
+/// The next slower preset of [`SPEED_PRESETS`]
+pub fn slower(speed: f64) -> f64 {
+    SPEED_PRESETS
+        .into_iter()
+        .rev()
+        .find(|&preset| preset < speed)
+        .unwrap_or(speed)
+}
+
+pub struct TimeControlWindow;
+
+impl EditorWindow for TimeControlWindow {
+    type State = ();
+    const NAME: &'static str = "Time";
+    const DEFAULT_SIZE: (f32, f32) = (280.0, 180.0);
+
+    fn ui(world: &mut World, _cx: EditorWindowContext, ui: &mut egui::Ui) {
+        egui::Grid::new("time control")
+            .num_columns(2)
+            .show(ui, |ui| {
+                let mut time = world.resource_mut::<Time<Virtual>>();
+                ui.label("Paused");
+                let mut paused = time.is_paused();
+                if ui.checkbox(&mut paused, "").changed() {
+                    toggle_pause(&mut time);
+                }
+                ui.end_row();
+
+                ui.label("Speed");
+                let mut speed = time.relative_speed_f64();
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::DragValue::new(&mut speed)
+                            .range(0.0..=20.0)
+                            .speed(0.1),
+                    );
+                    egui::ComboBox::from_id_salt("speed presets")
+                        .selected_text("Presets")
+                        .show_ui(ui, |ui| speed_presets_ui(&mut speed, ui));
+                });
+                if speed != time.relative_speed_f64() {
+                    time.set_relative_speed_f64(speed);
+                }
+                ui.end_row();
+
+                let mut time_control = world.resource_mut::<TimeControl>();
+                ui.label("Step frames");
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut time_control.step_count).range(1..=1000));
+                    if ui.button("Step").clicked() {
+                        let frames = time_control.step_count;
+                        time_control.step_frames(frames);
+                    }
+                });
+                ui.end_row();
+
+                ui.label("Fixed timestep");
+                let mut hz = 1.0 / world.resource::<Time<Fixed>>().timestep().as_secs_f64();
+                let (hz_changed, tick) = ui
+                    .horizontal(|ui| {
+                        let hz_changed = ui
+                            .add(
+                                egui::DragValue::new(&mut hz)
+                                    .range(1.0..=1000.0)
+                                    .speed(0.5)
+                                    .suffix(" Hz"),
+                            )
+                            .changed();
+                        let tick = ui.button("Tick").on_hover_text("Run FixedUpdate once");
+                        (hz_changed, tick.clicked())
+                    })
+                    .inner;
+                if hz_changed {
+                    world.resource_mut::<Time<Fixed>>().set_timestep_hz(hz);
+                }
+                if tick {
+                    world.resource_mut::<TimeControl>().step_fixed_update();
+                }
+                ui.end_row();
+            });
+    }
+
+    fn viewport_toolbar_ui(world: &mut World, _cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let mut time = world.resource_mut::<Time<Virtual>>();
+        let (icon, hover) = if time.is_paused() {
+            ("▶", "Resume time")
+        } else {
+            ("⏸", "Pause time")
+        };
+        if ui.button(icon).on_hover_text(hover).clicked() {
+            toggle_pause(&mut time);
+        }
+
+        let mut speed = time.relative_speed_f64();
+        ui.menu_button(speed_text(speed), |ui| speed_presets_ui(&mut speed, ui));
+        if speed != time.relative_speed_f64() {
+            time.set_relative_speed_f64(speed);
+        }
+
+        let mut time_control = world.resource_mut::<TimeControl>();
+        if ui.button("⏭").on_hover_text("Step 1 frame").clicked() {
+            time_control.step_frames(1);
+        }
+        let step_count = time_control.step_count;
+        let step_n = ui
+            .button(format!("⏭{step_count}"))
+            .on_hover_text(format!("Step {step_count} frames"));
+        if step_n.clicked() {
+            time_control.step_frames(step_count);
+        }
+        if ui
+            .button("⏩")
+            .on_hover_text("Run FixedUpdate once")
+            .clicked()
+        {
+            time_control.step_fixed_update();
+        }
+    }
+
+    fn app_setup(app: &mut App) {
+        app.init_resource::<TimeControl>()
+            .add_systems(First, step_frames.before(TimeSystem))
+            .add_systems(
+                RunFixedMainLoop,
+                step_fixed_update.in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop),
+            );
+    }
+}
+
+fn speed_presets_ui(speed: &mut f64, ui: &mut egui::Ui) {
+    for preset in SPEED_PRESETS {
+        if ui
+            .selectable_value(speed, preset, speed_text(preset))
+            .clicked()
+        {
+            ui.close_menu();
+        }
+    }
+}
+
+fn speed_text(speed: f64) -> String {
+    format!("{}x", (speed * 100.0).round() / 100.0)
+}
+
+/// Unpauses virtual time while there are frames to step, and pauses it after the last one
+fn step_frames(mut time_control: ResMut<TimeControl>, mut time: ResMut<Time<Virtual>>) {
+    if time_control.pending_frames > 0 {
+        time_control.pending_frames -= 1;
+        time_control.stepping = true;
+        time.unpause();
+    } else if time_control.stepping {
+        time_control.stepping = false;
+        time.pause();
+    }
+}
+
+/// Runs [`FixedMain`] the same way the fixed main loop does, for every requested fixed update step
+fn step_fixed_update(world: &mut World) {
+    let mut time_control = world.resource_mut::<TimeControl>();
+    if time_control.pending_fixed_updates == 0 {
+        return;
+    }
+    time_control.pending_fixed_updates -= 1;
+
+    let mut fixed_time = world.resource_mut::<Time<Fixed>>();
+    let timestep = fixed_time.timestep();
+    fixed_time.advance_by(timestep);
+
+    *world.resource_mut::<Time>() = world.resource::<Time<Fixed>>().as_generic();
+    world.run_schedule(FixedMain);
+    *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+}
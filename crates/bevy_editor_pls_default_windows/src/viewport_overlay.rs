@@ -0,0 +1,111 @@
+//! Helpers for drawing over the viewports in world coordinates, e.g. from [`EditorWindow::viewport_ui`].
+//!
+//! [`EditorWindow::viewport_ui`]: bevy_editor_pls_core::editor_window::EditorWindow::viewport_ui
+
+use bevy::prelude::*;
+use bevy_editor_pls_core::{editor_window::EditorWindowContext, Editor};
+use bevy_inspector_egui::egui;
+
+use crate::cameras::{rect_to_viewport_position, ActiveEditorCamera, EditorCamera, EditorViewport};
+
+/// Maps between world space and egui coordinates inside of a viewport, using the editor camera shown in it.
+///
+/// ```rust,ignore
+/// fn viewport_ui(world: &mut World, cx: EditorWindowContext, ui: &mut egui::Ui) {
+///     let Some(projection) = ViewportProjection::of_viewport(world, &cx) else {
+///         return;
+///     };
+///     let painter = cx.viewport_painter(ui).unwrap();
+///     if let Some(pos) = projection.world_to_viewport(Vec3::ZERO) {
+///         painter.circle_filled(pos, 4.0, egui::Color32::RED);
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ViewportProjection {
+    camera: Camera,
+    transform: GlobalTransform,
+    rect: egui::Rect,
+}
+
+impl ViewportProjection {
+    /// Projection of `camera`, rendering to the viewport at `rect`
+    pub fn new(camera: Camera, transform: GlobalTransform, rect: egui::Rect) -> Self {
+        ViewportProjection {
+            camera,
+            transform,
+            rect,
+        }
+    }
+
+    /// Projection of the current editor camera of the viewport whose toolbar or overlay is being drawn
+    pub fn of_viewport(world: &mut World, cx: &EditorWindowContext) -> Option<Self> {
+        let rect = cx.viewport_rect()?;
+        let mut cameras = world
+            .query_filtered::<(&Camera, &GlobalTransform, &EditorViewport), With<EditorCamera>>();
+        let (camera, transform, _) = cameras
+            .iter(world)
+            .find(|(camera, _, viewport)| camera.is_active && viewport.0 == cx.viewport())?;
+        Some(ViewportProjection::new(camera.clone(), *transform, rect))
+    }
+
+    /// Projection of the [`ActiveEditorCamera`], for use outside of the editor ui
+    pub fn active(world: &mut World) -> Option<Self> {
+        let rect = world.get_resource::<Editor>()?.viewport();
+        let mut cameras =
+            world.query_filtered::<(&Camera, &GlobalTransform), With<ActiveEditorCamera>>();
+        let (camera, transform) = cameras.iter(world).next()?;
+        Some(ViewportProjection::new(camera.clone(), *transform, rect))
+    }
+
+    pub fn camera(&self) -> (&Camera, &GlobalTransform) {
+        (&self.camera, &self.transform)
+    }
+
+    /// Rect of the viewport in egui coordinates
+    pub fn rect(&self) -> egui::Rect {
+        self.rect
+    }
+
+    /// Position of `position` on screen, or `None` if it is behind the camera or not inside of the viewport
+    pub fn world_to_viewport(&self, position: Vec3) -> Option<egui::Pos2> {
+        let viewport_position = self
+            .camera
+            .world_to_viewport(&self.transform, position)
+            .ok()?;
+        let pos = self.camera_viewport_to_egui(viewport_position)?;
+        self.rect.contains(pos).then_some(pos)
+    }
+
+    /// The ray going through `pos` into the scene, for picking in 3d
+    pub fn viewport_to_world(&self, pos: egui::Pos2) -> Option<Ray3d> {
+        let viewport_position = self.egui_to_camera_viewport(pos)?;
+        self.camera
+            .viewport_to_world(&self.transform, viewport_position)
+            .ok()
+    }
+
+    /// The world position at `pos`, for 2d cameras
+    pub fn viewport_to_world_2d(&self, pos: egui::Pos2) -> Option<Vec2> {
+        let viewport_position = self.egui_to_camera_viewport(pos)?;
+        self.camera
+            .viewport_to_world_2d(&self.transform, viewport_position)
+            .ok()
+    }
+
+    /// The ray going through the pointer into the scene, if the pointer is over the viewport
+    pub fn cursor_ray(&self, ctx: &egui::Context) -> Option<Ray3d> {
+        let pointer = ctx.input(|input| input.pointer.hover_pos())?;
+        self.viewport_to_world(pointer)
+    }
+
+    /// Egui coordinates to the logical viewport coordinates of the camera, see [`crate::cameras::egui_to_viewport_position`]
+    fn egui_to_camera_viewport(&self, pos: egui::Pos2) -> Option<Vec2> {
+        rect_to_viewport_position(self.rect, &self.camera, pos)
+    }
+
+    fn camera_viewport_to_egui(&self, viewport_position: Vec2) -> Option<egui::Pos2> {
+        let normalized = viewport_position / self.camera.logical_viewport_size()?;
+        Some(self.rect.min + egui::vec2(normalized.x, normalized.y) * self.rect.size())
+    }
+}
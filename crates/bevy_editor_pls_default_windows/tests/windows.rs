@@ -0,0 +1,353 @@
+use bevy::ecs::event::EventCursor;
+use bevy::prelude::*;
+use bevy_editor_pls_core::editor_window::EditorWindow;
+use bevy_editor_pls_core::layout::{DockNode, Layout};
+use bevy_editor_pls_core::testing::EditorTestApp;
+use bevy_editor_pls_core::theme::ThemePreset;
+use bevy_editor_pls_core::undo::UndoStack;
+use bevy_editor_pls_core::{
+    Editor, EditorEvent, EditorSet, EditorTheme, Notification, Notifications, PopOutWindow,
+};
+use bevy_editor_pls_default_windows::debug_settings::DebugSettingsWindow;
+use bevy_editor_pls_default_windows::hierarchy::HierarchyWindow;
+use bevy_editor_pls_default_windows::inspector::InspectorWindow;
+use bevy_editor_pls_default_windows::play_mode::{PlayMode, PlayModePlugin};
+use bevy_editor_pls_default_windows::preferences::PreferencesWindow;
+use bevy_editor_pls_default_windows::scenes::SceneWindow;
+use bevy_editor_pls_default_windows::time_control::{TimeControl, TimeControlWindow};
+use bevy_inspector_egui::egui;
+
+fn editor_showing<W: EditorWindow>() -> EditorTestApp {
+    let mut editor = EditorTestApp::new();
+    editor
+        .add_window::<HierarchyWindow>()
+        .add_window::<InspectorWindow>()
+        .show_window::<W>();
+    editor
+}
+
+/// Label of `entity` in the hierarchy, which shows the id next to the name
+fn hierarchy_label(name: &str, entity: Entity) -> String {
+    format!("{name} ({entity})")
+}
+
+#[test]
+fn hierarchy_lists_named_entities() {
+    let mut editor = editor_showing::<HierarchyWindow>();
+    let player = editor.world_mut().spawn(Name::new("Player")).id();
+    editor.run_frames(2);
+
+    assert!(editor.contains_text(&hierarchy_label("Player", player)));
+}
+
+#[test]
+fn clicking_an_entity_in_the_hierarchy_selects_it() {
+    let mut editor = editor_showing::<HierarchyWindow>();
+    let player = editor.world_mut().spawn(Name::new("Player")).id();
+    editor
+        .run_frames(2)
+        .click(&hierarchy_label("Player", player));
+
+    let state = editor.window_state::<HierarchyWindow>().unwrap();
+    assert!(state.selected.contains(player));
+}
+
+#[test]
+fn deleting_from_the_hierarchy_can_be_undone() {
+    let mut editor = editor_showing::<HierarchyWindow>();
+    let player = editor.world_mut().spawn(Name::new("Player")).id();
+    editor
+        .run_frames(2)
+        .click(&hierarchy_label("Player", player))
+        .press_key(egui::Key::Delete);
+    assert!(editor.world().get_entity(player).is_err());
+
+    assert!(UndoStack::undo(editor.world_mut()));
+    let mut names = editor.world_mut().query::<&Name>();
+    let names: Vec<_> = names.iter(editor.world()).map(Name::as_str).collect();
+    assert_eq!(names, ["Player"]);
+}
+
+#[test]
+fn locked_inspector_ignores_selection_changes() {
+    let mut editor = editor_showing::<InspectorWindow>();
+    let player = editor.world_mut().spawn(Name::new("Player")).id();
+    let enemy = editor.world_mut().spawn(Name::new("Enemy")).id();
+
+    let mut hierarchy = editor.window_state_mut::<HierarchyWindow>().unwrap();
+    hierarchy.selected.select_replace(player);
+    editor.run_frames(2).click("🔒 Lock");
+
+    let mut hierarchy = editor.window_state_mut::<HierarchyWindow>().unwrap();
+    hierarchy.selected.select_replace(enemy);
+    editor.update();
+
+    let inspector = editor.window_state::<InspectorWindow>().unwrap();
+    assert_eq!(inspector.locked, Some(vec![player]));
+}
+
+#[test]
+fn status_bar_shows_selection_and_latest_notification() {
+    let mut editor = editor_showing::<HierarchyWindow>();
+    let player = editor.world_mut().spawn(Name::new("Player")).id();
+    editor
+        .run_frames(2)
+        .click(&hierarchy_label("Player", player));
+    assert!(editor.contains_text("1 entity selected"));
+
+    editor
+        .world_mut()
+        .send_event(Notification::warning("Low on memory"));
+    editor.run_frames(2);
+    assert_eq!(
+        editor
+            .world()
+            .resource::<Notifications>()
+            .latest()
+            .unwrap()
+            .message,
+        "Low on memory"
+    );
+    assert!(editor.contains_text("Low on memory"));
+}
+
+#[test]
+fn new_viewports_open_next_to_the_primary_viewport() {
+    let mut editor = EditorTestApp::new();
+    let viewport = editor.internal_state_mut().open_viewport();
+    editor.run_frames(2);
+
+    let viewports: Vec<_> = editor.editor().viewports().collect();
+    assert_eq!(viewports.len(), 2);
+    let (primary, secondary) = (viewports[0], viewports[1]);
+    assert_eq!((primary.0, secondary.0), (0, viewport));
+    assert!(primary.1.right() <= secondary.1.left());
+}
+
+#[test]
+fn selecting_and_deleting_sends_editor_events() {
+    let mut editor = editor_showing::<HierarchyWindow>();
+    let player = editor.world_mut().spawn(Name::new("Player")).id();
+    let mut cursor = EventCursor::<EditorEvent>::default();
+    let mut read_events = |editor: &EditorTestApp| -> Vec<EditorEvent> {
+        let events = editor.world().resource::<Events<EditorEvent>>();
+        cursor.read(events).cloned().collect()
+    };
+
+    editor
+        .run_frames(2)
+        .click(&hierarchy_label("Player", player));
+    let events = read_events(&editor);
+    assert!(events.iter().any(|event| matches!(
+        event,
+        EditorEvent::SelectionChanged { selected } if *selected == [player]
+    )));
+
+    editor.press_key(egui::Key::Delete);
+    let events = read_events(&editor);
+    assert!(events.iter().any(|event| matches!(
+        *event,
+        EditorEvent::EntityDespawned { entity, recursive: true } if entity == player
+    )));
+}
+
+#[test]
+fn persisted_window_states_are_restored() {
+    let mut editor = EditorTestApp::new();
+    editor.add_window::<DebugSettingsWindow>();
+    let mut state = editor.window_state_mut::<DebugSettingsWindow>().unwrap();
+    state.highlight_selected = false;
+    state.pause_time = true;
+    let saved = editor.editor().save_window_states();
+    assert!(saved.contains_key(DebugSettingsWindow::ID));
+
+    let mut restarted = EditorTestApp::new();
+    restarted.add_window::<DebugSettingsWindow>();
+    restarted
+        .world_mut()
+        .resource_mut::<Editor>()
+        .load_window_states(&saved);
+
+    let state = restarted.window_state::<DebugSettingsWindow>().unwrap();
+    assert!(!state.highlight_selected);
+    assert!(!state.pause_time, "pausing time is not persisted");
+}
+
+#[test]
+fn leaving_play_mode_restores_the_scene() {
+    let mut editor = editor_showing::<HierarchyWindow>();
+    editor
+        .add_plugins(PlayModePlugin)
+        .world_mut()
+        .insert_resource(PlayMode::new());
+    let player = editor.world_mut().spawn(Name::new("Player")).id();
+    editor.run_frames(1);
+    let mut hierarchy = editor.window_state_mut::<HierarchyWindow>().unwrap();
+    hierarchy.selected.select_replace(player);
+
+    editor
+        .world_mut()
+        .send_event(EditorEvent::Toggle { now_active: false });
+    editor.run_frames(1);
+    assert!(editor.world().resource::<PlayMode>().is_playing());
+
+    editor
+        .world_mut()
+        .entity_mut(player)
+        .insert(Name::new("Dead player"));
+    let bullet = editor.world_mut().spawn(Name::new("Bullet")).id();
+    editor
+        .world_mut()
+        .send_event(EditorEvent::Toggle { now_active: true });
+    editor.run_frames(1);
+
+    let world = editor.world();
+    assert!(!world.resource::<PlayMode>().is_playing());
+    assert_eq!(world.get::<Name>(player).unwrap().as_str(), "Player");
+    assert!(world.get_entity(bullet).is_err());
+    let hierarchy = editor.window_state::<HierarchyWindow>().unwrap();
+    assert_eq!(hierarchy.selected.as_slice(), &[player]);
+}
+
+#[test]
+fn stepping_frames_advances_paused_time() {
+    let mut editor = EditorTestApp::new();
+    editor.add_window::<TimeControlWindow>().run_frames(1);
+    editor.world_mut().resource_mut::<Time<Virtual>>().pause();
+    editor.run_frames(1);
+    let paused_at = editor.world().resource::<Time<Virtual>>().elapsed();
+
+    editor
+        .world_mut()
+        .resource_mut::<TimeControl>()
+        .step_frames(2);
+    editor.run_frames(3);
+    let time = editor.world().resource::<Time<Virtual>>();
+    assert!(time.is_paused());
+    let stepped_to = time.elapsed();
+    assert!(stepped_to > paused_at);
+
+    editor.run_frames(2);
+    assert_eq!(
+        editor.world().resource::<Time<Virtual>>().elapsed(),
+        stepped_to
+    );
+}
+
+#[test]
+fn popped_out_windows_dock_back_when_closed() {
+    let mut editor = editor_showing::<InspectorWindow>();
+    editor.run_frames(1);
+    assert!(editor.contains_text("Inspector"));
+
+    editor
+        .internal_state_mut()
+        .pop_out_window::<InspectorWindow>();
+    editor.run_frames(2);
+    let pop_out_window = editor
+        .world_mut()
+        .query_filtered::<Entity, With<PopOutWindow>>()
+        .single(editor.world());
+    assert!(!editor.contains_text("Inspector"));
+
+    editor.world_mut().despawn(pop_out_window);
+    editor.run_frames(2);
+    assert!(editor.contains_text("Inspector"));
+}
+
+#[test]
+fn popping_out_a_floating_window_closes_it() {
+    let mut editor = EditorTestApp::new();
+    editor.add_window::<InspectorWindow>();
+    editor
+        .internal_state_mut()
+        .layout_mut()
+        .add_floating_window::<InspectorWindow>();
+    // egui windows are invisible in their first frame, while they are sized
+    editor.run_frames(2);
+    assert!(editor.contains_text("Inspector"));
+
+    editor
+        .internal_state_mut()
+        .pop_out_window::<InspectorWindow>();
+    editor.run_frames(2);
+    assert!(!editor.contains_text("Inspector"));
+}
+
+#[test]
+fn typing_into_the_scene_filename() {
+    let mut editor = EditorTestApp::new();
+    editor
+        .add_window::<SceneWindow>()
+        .show_window::<SceneWindow>()
+        .run_frames(2)
+        .type_into("scene.scn.ron", "level.scn.ron");
+
+    assert!(editor.contains_text("level.scn.ron"));
+    let saved = editor.editor().save_window_states();
+    assert!(saved[SceneWindow::ID].contains("level.scn.ron"));
+}
+
+#[test]
+fn preferences_switch_the_theme() {
+    let mut editor = EditorTestApp::new();
+    editor
+        .add_window::<PreferencesWindow>()
+        .show_window::<PreferencesWindow>()
+        .run_frames(2)
+        .click("Dark")
+        .click("Light");
+
+    let theme = editor.world().resource::<EditorTheme>();
+    assert_eq!(theme.preset, ThemePreset::Light);
+}
+
+#[test]
+fn layouts_are_built_from_dock_descriptions() {
+    let mut editor = EditorTestApp::new();
+    editor
+        .add_window::<HierarchyWindow>()
+        .add_window::<InspectorWindow>();
+    let dock = DockNode::Viewport
+        .split_left(0.2, DockNode::window::<HierarchyWindow>())
+        .split_right(0.75, DockNode::window::<InspectorWindow>());
+    let dock = dock
+        .retain_windows(&|window| window != std::any::TypeId::of::<HierarchyWindow>())
+        .unwrap();
+    *editor.internal_state_mut().layout_mut() = Layout::from_dock(&dock);
+    editor.run_frames(2);
+
+    assert!(editor.contains_text("Inspector"));
+    assert!(!editor.contains_text("Hierarchy"));
+    let viewports: Vec<_> = editor.editor().viewports().collect();
+    assert_eq!(viewports.len(), 1);
+    let inspector = editor.find_text("Inspector").unwrap();
+    assert!(viewports[0].1.right() <= inspector.left());
+}
+
+#[test]
+fn editor_sets_run_depending_on_whether_the_editor_is_active() {
+    #[derive(Resource, Default)]
+    struct Runs {
+        active: u32,
+        inactive: u32,
+    }
+
+    let mut editor = EditorTestApp::new();
+    editor.app.init_resource::<Runs>().add_systems(
+        Update,
+        (
+            (|mut runs: ResMut<Runs>| runs.active += 1).in_set(EditorSet::Active),
+            (|mut runs: ResMut<Runs>| runs.inactive += 1).in_set(EditorSet::Inactive),
+        ),
+    );
+    editor.run_frames(2);
+    editor
+        .world_mut()
+        .resource_mut::<Editor>()
+        .set_active(false);
+    editor.run_frames(3);
+
+    let runs = editor.world().resource::<Runs>();
+    assert_eq!((runs.active, runs.inactive), (2, 3));
+}